@@ -42,6 +42,33 @@ where
     Ok(dataset)
 }
 
+/// Loads every `.txt` file directly inside `dir` (no per-author subdirectories), for use with
+/// [`stal_core::model::cluster`] on corpora that have no ground-truth author labels.
+pub fn load_unlabeled_dataset<P: AsRef<Path>>(dir: P) -> Result<Vec<String>, DatasetLoadError> {
+    log::trace!("Normalize path: `{}`.", dir.as_ref().display());
+    let path = dir.as_ref().normalize()?;
+
+    let mut paths = vec![];
+    log::trace!("Iter all texts.");
+    let mut texts = WalkDir::new(path).max_depth(1).into_iter();
+    texts.next(); // Skip self
+    for text in texts {
+        let text = text?;
+        log::trace!("Load Text: {:?}", text.path());
+        let path = text.path().to_path_buf();
+        if path.extension().is_some_and(|ext| ext == "txt") {
+            let path = path
+                .to_str()
+                .ok_or_else(|| DatasetLoadError::InvalidFileName(path.clone()))?
+                .to_string();
+            log::trace!("Find text: `{}`.", path);
+            paths.push(path);
+        }
+    }
+
+    Ok(paths)
+}
+
 pub fn load_dir_dataset<P: AsRef<Path>>(dir: P) -> Result<Vec<(String, String)>, DatasetLoadError> {
     log::trace!("Normalize path: `{}`.", dir.as_ref().display());
     let path = dir.as_ref().normalize()?;
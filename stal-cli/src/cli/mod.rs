@@ -1,8 +1,8 @@
 use std::path::PathBuf;
 
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 
-use crate::cli::model::{classify, train};
+use crate::cli::model::{classify, cluster, merge, train, update};
 
 mod dataset;
 mod model;
@@ -35,6 +35,59 @@ pub enum Commands {
         ///
         /// Recommended suffix is `.postcard`, and `.model` is also acceptable.
         save_path: PathBuf,
+
+        #[arg(long, value_enum, default_value_t = ScoringMode::Fisher, help = "scoring mode used to combine per-token evidence")]
+        /// How per-token evidence is combined into a per-author rating.
+        ///
+        /// `fisher` is the original heuristic product-of-probabilities combiner. `mnb` is a
+        /// proper multinomial naive Bayes scorer in log-space, which is more stable on longer
+        /// documents.
+        scoring_mode: ScoringMode,
+
+        #[arg(long, value_delimiter = ',', help = "character n-gram sizes to extract, e.g. 2,3,4")]
+        /// Character n-gram sizes (in chars, including spaces/punctuation) to extract alongside
+        /// word unigrams.
+        char_ngrams: Vec<usize>,
+
+        #[arg(long, help = "also extract word bigrams w[i] w[i+1]")]
+        /// Whether to additionally extract word bigrams `w[i] w[i+1]`.
+        word_bigrams: bool,
+
+        #[arg(long, help = "also extract learned BPE subwords, targeting this vocabulary size")]
+        /// Target subword vocabulary size for the BPE feature family; see
+        /// [`stal_core::model::BpeConfig::vocab_size`]. Enables the feature family.
+        bpe_vocab_size: Option<usize>,
+
+        #[arg(
+            long,
+            default_value_t = 2,
+            help = "minimum pair frequency to keep BPE-merging; `--bpe-vocab-size` only"
+        )]
+        /// Minimum adjacent-symbol-pair frequency required to keep merging; see
+        /// [`stal_core::model::BpeConfig::min_frequency`].
+        bpe_min_frequency: u32,
+
+        #[arg(long, value_enum, default_value_t = ModelKind::Bayes, help = "kind of model to train")]
+        /// Which kind of model to train.
+        ///
+        /// `bayes` is the smoothed naive Bayes model. `perceptron` is a linear discriminative
+        /// classifier trained with the averaged perceptron, which typically wins on stylometry.
+        model_kind: ModelKind,
+
+        #[arg(long, value_enum, help = "language-specific stopword list and stemmer to apply before counting tokens")]
+        /// Applies a language's built-in stopword list and Snowball stemmer before counting
+        /// tokens, via [`stal_core::model::TokenPipeline::for_language`]. Omit to keep raw
+        /// `charabia` segments (the original behavior).
+        language: Option<Language>,
+
+        #[arg(
+            long,
+            help = "how strongly a fuzzy vocabulary match counts towards a rating, relative to an exact match; `bayes` model kind only"
+        )]
+        /// How strongly a fuzzy (non-exact, edit-distance-bounded) vocabulary match counts
+        /// towards a rating, relative to an exact match. `bayes` model kind only; see
+        /// [`stal_core::model::BayesianModel::set_fuzzy_discount`].
+        fuzzy_discount: Option<f32>,
     },
     #[command(arg_required_else_help = true)]
     Classify {
@@ -42,6 +95,10 @@ pub enum Commands {
         /// The path to save the model.
         model: PathBuf,
 
+        #[arg(long, value_enum, default_value_t = ModelKind::Bayes, help = "kind of model being loaded")]
+        /// Which kind of model `model` is. Must match the kind it was trained as.
+        model_kind: ModelKind,
+
         #[arg(help = "text to be classified")]
         /// The text to be classified.
         ///
@@ -73,25 +130,202 @@ pub enum Commands {
         /// This outputs the classified result in a more concise way, showing just the most familiar
         /// author and the possibility.
         concise: bool,
+
+        #[arg(
+            long,
+            value_delimiter = ',',
+            help = "author labels, in fine-tuning order (transformer model kind only)"
+        )]
+        /// Author labels, in the order the checkpoint was fine-tuned with. Only used when
+        /// `--model-kind transformer`; the other model kinds embed their author list.
+        authors: Vec<String>,
+
+        #[arg(long, help = "show the top-k most probable author sequences via beam search")]
+        /// Show the `k` most probable full author-assignment sequences via beam search, instead
+        /// of one blended total distribution. Useful for spotting mixed-authorship documents.
+        top_k: Option<usize>,
+
+        #[arg(
+            long,
+            help = "score authors by compression distance (MDL) instead of the model's scoring mode; `bayes` model kind only"
+        )]
+        /// Score authors by compression distance (minimum description length) via
+        /// [`stal_core::model::BayesianModel::classify_text_mdl`] instead of the model's stored
+        /// [`stal_core::model::ScoringMode`]. Only supported with `--model-kind bayes`.
+        mdl: bool,
+    },
+    #[command(arg_required_else_help = true)]
+    Cluster {
+        #[arg(help = "directory of `.txt` files with no author labels")]
+        /// A directory of `.txt` files with no author labels, to be grouped into inferred
+        /// authors via [`stal_core::model::cluster`]. Unlike `Train`'s directory dataset, this
+        /// directory has no per-author subdirectories; every `.txt` file directly inside it is
+        /// one document.
+        directory: String,
+
+        #[arg(long, default_value_t = 10, help = "maximum number of clusters to consider")]
+        /// Upper bound on the number of clusters; empty clusters die out naturally, so the
+        /// inferred author count may be smaller.
+        k_max: usize,
+
+        #[arg(long, default_value_t = 0.1, help = "Dirichlet concentration over cluster sizes")]
+        /// Smooths the prior over cluster sizes. Higher values favor more evenly sized clusters.
+        alpha: f32,
+
+        #[arg(long, default_value_t = 0.1, help = "Dirichlet concentration over word frequencies")]
+        /// Smooths the per-cluster word-frequency likelihood.
+        beta: f32,
+
+        #[arg(long, default_value_t = 10, help = "number of Gibbs sampling passes over the corpus")]
+        /// How many passes to make over the corpus, re-sampling each document's cluster.
+        iterations: usize,
+    },
+    #[command(arg_required_else_help = true)]
+    Update {
+        #[arg(help = "path to the model to update in place; `bayes` model kind only")]
+        /// The path to the model to update in place. Only `bayes` models support incremental
+        /// updates; see [`stal_core::model::BayesianModel::update`].
+        model: PathBuf,
+
+        #[arg(help = "author of the new document")]
+        /// Author of the new document being folded into the model.
+        author: String,
+
+        #[arg(help = "path to the new document, as pure text")]
+        /// Path to the new document to fold into the model, as pure text.
+        text_path: PathBuf,
+    },
+    #[command(arg_required_else_help = true)]
+    Merge {
+        #[arg(help = "path to the model to merge into; `bayes` model kind only")]
+        /// The path to the model that `shard` is merged into and saved back to. Only `bayes`
+        /// models support merging; see [`stal_core::model::BayesianModel::merge`].
+        model: PathBuf,
+
+        #[arg(help = "path to the independently trained model shard to merge in")]
+        /// Path to the independently trained model shard to merge into `model`.
+        shard: PathBuf,
     },
 }
 
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ScoringMode {
+    /// Fisher-style product-of-probabilities combiner (the original heuristic).
+    Fisher,
+    /// Multinomial naive Bayes scoring in log-space with Laplace smoothing.
+    Mnb,
+}
+
+impl From<ScoringMode> for stal_core::model::ScoringMode {
+    fn from(mode: ScoringMode) -> Self {
+        match mode {
+            ScoringMode::Fisher => stal_core::model::ScoringMode::Fisher,
+            ScoringMode::Mnb => stal_core::model::ScoringMode::MultinomialNaiveBayes,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Language {
+    English,
+    SimplifiedChinese,
+}
+
+impl From<Language> for stal_core::model::Language {
+    fn from(language: Language) -> Self {
+        match language {
+            Language::English => stal_core::model::Language::English,
+            Language::SimplifiedChinese => stal_core::model::Language::SimplifiedChinese,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ModelKind {
+    /// Smoothed naive Bayes model.
+    Bayes,
+    /// Averaged-perceptron discriminative classifier.
+    Perceptron,
+    /// Transformer sequence-classification backend. Requires the `transformer` cargo feature and
+    /// loads a previously fine-tuned checkpoint; `Train` does not fine-tune it in this path.
+    #[cfg(feature = "transformer")]
+    Transformer,
+}
+
 impl Cli {
     pub fn execute() -> anyhow::Result<()> {
         let cli = Cli::parse();
         match cli.command {
-            Commands::Train { dataset, save_path } => {
+            Commands::Train {
+                dataset,
+                save_path,
+                scoring_mode,
+                char_ngrams,
+                word_bigrams,
+                bpe_vocab_size,
+                bpe_min_frequency,
+                model_kind,
+                language,
+                fuzzy_discount,
+            } => {
                 log::trace!("`train` command.");
-                train(dataset, save_path)
+                let feature_config = stal_core::model::FeatureConfig {
+                    char_ngram_sizes: char_ngrams,
+                    word_bigrams,
+                    bpe: bpe_vocab_size.map(|vocab_size| stal_core::model::BpeConfig {
+                        vocab_size,
+                        min_frequency: bpe_min_frequency,
+                        merges: Vec::new(),
+                    }),
+                };
+                let pipeline = match language {
+                    Some(language) => stal_core::model::TokenPipeline::for_language(language.into()),
+                    None => stal_core::model::TokenPipeline::default(),
+                };
+                train(
+                    dataset,
+                    save_path,
+                    scoring_mode.into(),
+                    feature_config,
+                    model_kind,
+                    pipeline,
+                    fuzzy_discount,
+                )
             }
             Commands::Classify {
                 model,
+                model_kind,
                 text,
                 rich,
                 concise,
+                authors,
+                top_k,
+                mdl,
             } => {
                 log::trace!("`classify` command.");
-                classify(model, text, rich, concise)
+                classify(model, model_kind, text, rich, concise, authors, top_k, mdl)
+            }
+            Commands::Cluster {
+                directory,
+                k_max,
+                alpha,
+                beta,
+                iterations,
+            } => {
+                log::trace!("`cluster` command.");
+                cluster(directory, k_max, alpha, beta, iterations)
+            }
+            Commands::Update {
+                model,
+                author,
+                text_path,
+            } => {
+                log::trace!("`update` command.");
+                update(model, author, text_path)
+            }
+            Commands::Merge { model, shard } => {
+                log::trace!("`merge` command.");
+                merge(model, shard)
             }
         }
     }
@@ -1,10 +1,19 @@
 use std::io::{IsTerminal, Read};
 use std::path::PathBuf;
 
-use crate::cli::dataset::{load_dir_dataset, load_json_dataset};
+use crate::cli::dataset::{load_dir_dataset, load_json_dataset, load_unlabeled_dataset};
+use crate::cli::ModelKind;
 
 /// Train the model
-pub fn train(dataset: String, save_path: PathBuf) -> anyhow::Result<()> {
+pub fn train(
+    dataset: String,
+    save_path: PathBuf,
+    scoring_mode: stal_core::model::ScoringMode,
+    feature_config: stal_core::model::FeatureConfig,
+    model_kind: ModelKind,
+    pipeline: stal_core::model::TokenPipeline,
+    fuzzy_discount: Option<f32>,
+) -> anyhow::Result<()> {
     log::trace!("Current dir: {:?}", std::env::current_dir()?);
     let dataset = if dataset.ends_with(".json") {
         log::trace!("Load dataset according to JSON configuration.");
@@ -14,13 +23,39 @@ pub fn train(dataset: String, save_path: PathBuf) -> anyhow::Result<()> {
         load_dir_dataset(dataset)
     }?;
     log::info!("Start training the model.");
-    let model = stal_core::model::BayesianModel::train(dataset)?;
-    log::info!("Model training finished: {}.", model);
-
-    log::info!("Saving model to `{}`", save_path.display());
     // Ensure that the path used for saving model exists.
     fsio::file::ensure_exists(&save_path)?;
-    model.save(&save_path)?;
+    match model_kind {
+        ModelKind::Bayes => {
+            let mut model = stal_core::model::BayesianModel::train_with_config(
+                dataset,
+                feature_config,
+                pipeline,
+            )?;
+            model.set_scoring_mode(scoring_mode);
+            if let Some(fuzzy_discount) = fuzzy_discount {
+                model.set_fuzzy_discount(fuzzy_discount);
+            }
+            log::info!("Model training finished: {}.", model);
+            model.save(&save_path)?;
+        }
+        ModelKind::Perceptron => {
+            let model = stal_core::model::PerceptronModel::train_with_config(
+                dataset,
+                feature_config,
+                pipeline,
+                stal_core::model::DEFAULT_EPOCHS,
+            )?;
+            log::info!("Model training finished: {}.", model);
+            model.save(&save_path)?;
+        }
+        #[cfg(feature = "transformer")]
+        ModelKind::Transformer => {
+            anyhow::bail!(
+                "`train` cannot fine-tune a transformer checkpoint yet; fine-tune it separately and point `classify --model-kind transformer` at the result."
+            );
+        }
+    }
     log::info!("Model saved.");
     Ok(())
 }
@@ -28,22 +63,120 @@ pub fn train(dataset: String, save_path: PathBuf) -> anyhow::Result<()> {
 /// Classify using the specified model
 pub fn classify(
     model: PathBuf,
+    model_kind: ModelKind,
     text: Option<String>,
     _rich: bool,
     _concise: bool,
+    authors: Vec<String>,
+    top_k: Option<usize>,
+    mdl: bool,
 ) -> anyhow::Result<()> {
     log::trace!("Get the input text.");
     let input = get_input(text)?;
     log::trace!("Load specified model.");
-    let model = stal_core::model::BayesianModel::load(model)?;
-    log::trace!("Start classification.");
-    let result = model.classify_text(&input);
+
+    if mdl {
+        let ModelKind::Bayes = model_kind else {
+            anyhow::bail!("`--mdl` is only supported with `--model-kind bayes`.");
+        };
+        let model = stal_core::model::BayesianModel::load(model)?;
+        log::trace!("Start MDL classification.");
+        let result = model.classify_text_mdl(&input);
+        // TODO: `rich` and `concise` output
+        println!("{:#?}", result);
+        return Ok(());
+    }
+
+    if let Some(k) = top_k {
+        let sequences = match model_kind {
+            ModelKind::Bayes => {
+                let model = stal_core::model::BayesianModel::load(model)?;
+                model.classify_text_top_k(&input, k)
+            }
+            ModelKind::Perceptron => {
+                let model = stal_core::model::PerceptronModel::load(model)?;
+                model.classify_text_top_k(&input, k)
+            }
+            #[cfg(feature = "transformer")]
+            ModelKind::Transformer => {
+                anyhow::bail!("`--top-k` is not yet supported with `--model-kind transformer`.");
+            }
+        };
+        // TODO: `rich` and `concise` output
+        println!("{:#?}", sequences);
+        return Ok(());
+    }
+
+    let result = match model_kind {
+        ModelKind::Bayes => {
+            let model = stal_core::model::BayesianModel::load(model)?;
+            log::trace!("Start classification.");
+            model.classify_text(&input)
+        }
+        ModelKind::Perceptron => {
+            let model = stal_core::model::PerceptronModel::load(model)?;
+            log::trace!("Start classification.");
+            model.classify_text(&input)
+        }
+        #[cfg(feature = "transformer")]
+        ModelKind::Transformer => {
+            let config = stal_core::model::transformer_config_from_checkpoint(&model)?;
+            let model = stal_core::model::TransformerModel::load(config, authors)?;
+            log::trace!("Start classification.");
+            model.classify_text(&input)
+        }
+    };
     // TODO: `rich` and `concise` output
     // TODO: Format classification result
     println!("{:#?}", result);
     Ok(())
 }
 
+/// Discover author groupings among unlabeled `.txt` files and print the inferred clusters.
+pub fn cluster(
+    directory: String,
+    k_max: usize,
+    alpha: f32,
+    beta: f32,
+    iterations: usize,
+) -> anyhow::Result<()> {
+    log::trace!("Current dir: {:?}", std::env::current_dir()?);
+    let paths = load_unlabeled_dataset(directory)?;
+    log::info!("Clustering {} documents into at most {} clusters.", paths.len(), k_max);
+    let result = stal_core::model::cluster(paths, k_max, alpha, beta, iterations)?;
+    log::info!("Clustering finished with {} surviving clusters.", result.top_tokens.len());
+    // TODO: `rich` and `concise` output
+    println!("{:#?}", result.dataset);
+    println!("{:#?}", result.top_tokens);
+    Ok(())
+}
+
+/// Fold one more document into a previously trained `bayes` model, in place, instead of
+/// retraining from the full dataset.
+pub fn update(model: PathBuf, author: String, text_path: PathBuf) -> anyhow::Result<()> {
+    log::trace!("Load model to update: `{}`.", model.display());
+    let mut bayesian_model = stal_core::model::BayesianModel::load(&model)?;
+    let text = std::fs::read_to_string(&text_path)?;
+    log::info!("Updating model with one document from author '{}'.", author);
+    bayesian_model.update(&author, &text);
+    bayesian_model.save(&model)?;
+    log::info!("Model updated: {}.", bayesian_model);
+    Ok(())
+}
+
+/// Merges an independently trained `bayes` model shard into `model`, in place, so large corpora
+/// can be indexed in parallel and combined afterwards.
+pub fn merge(model: PathBuf, shard: PathBuf) -> anyhow::Result<()> {
+    log::trace!("Load model to merge into: `{}`.", model.display());
+    let mut bayesian_model = stal_core::model::BayesianModel::load(&model)?;
+    log::trace!("Load shard to merge: `{}`.", shard.display());
+    let shard_model = stal_core::model::BayesianModel::load(&shard)?;
+    bayesian_model.merge(&shard_model);
+    bayesian_model.save(&model)?;
+    log::info!("Model merged: {}.", bayesian_model);
+    Ok(())
+}
+
 pub fn get_input(option: Option<String>) -> anyhow::Result<String> {
     if let Some(str) = option {
         if str.ends_with(".txt") {
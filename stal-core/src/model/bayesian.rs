@@ -8,17 +8,10 @@ use serde::{Deserialize, Serialize};
 use text_splitter::TextSplitter;
 use thiserror::Error;
 
-#[derive(Debug)]
-pub struct Predication {
-    pub sentences_predicate: Vec<(usize, Vec<f32>)>,
-    pub total_predicate: Vec<f32>,
-}
-
-#[derive(Debug)]
-pub struct Classification {
-    pub sentences_classification: Vec<(usize, HashMap<String, f32>)>,
-    pub total_classification: HashMap<String, f32>,
-}
+use crate::model::bpe;
+use crate::model::persist::{self, VersionedLoadError};
+use crate::model::trie::DynTrie;
+use crate::model::{Classification, Predication, TokenPipeline};
 
 #[derive(Debug, Error)]
 pub enum BayesianSaveError {
@@ -32,12 +25,73 @@ pub enum BayesianSaveError {
 
 #[derive(Debug, Error)]
 pub enum BayesianLoadError {
+    #[error("Not a stal model file (bad magic bytes).")]
+    BadMagic,
+    #[error(
+        "Model was saved by an incompatible format version ({found}); this build expects version {expected}."
+    )]
+    IncompatibleVersion { found: u16, expected: u16 },
+    #[error("File was saved as a different model kind; load it with the matching model type.")]
+    KindMismatch,
     #[error("Failed to deserialize model.")]
     Deserialization(#[from] postcard::Error),
     #[error("Failed to read model from file.")]
     IO(#[from] io::Error),
 }
 
+impl From<VersionedLoadError> for BayesianLoadError {
+    fn from(error: VersionedLoadError) -> Self {
+        match error {
+            VersionedLoadError::BadMagic => BayesianLoadError::BadMagic,
+            VersionedLoadError::IncompatibleVersion { found, expected } => {
+                BayesianLoadError::IncompatibleVersion { found, expected }
+            }
+            VersionedLoadError::KindMismatch => BayesianLoadError::KindMismatch,
+            VersionedLoadError::Deserialization(error) => {
+                BayesianLoadError::Deserialization(error)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+/// Selects how [`BayesianModel::predicate`] turns per-token evidence into a per-author rating.
+pub enum ScoringMode {
+    /// The original heuristic: a Fisher-style product of per-token ratings, clamped to
+    /// `[MIN_RATING, MAX_RATING]` and trimmed by [`BayesianModel::adjust_probabilities`].
+    Fisher,
+    /// Proper multinomial naive Bayes scoring in log-space with Laplace smoothing, normalized
+    /// with a numerically safe softmax. Prefer this for longer documents, where the Fisher
+    /// product underflows.
+    MultinomialNaiveBayes,
+}
+
+impl Default for ScoringMode {
+    fn default() -> Self {
+        ScoringMode::Fisher
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+/// Configures which feature families [`BayesianModel::tokenize`] extracts from text.
+///
+/// Each family is namespaced in [`BayesianModel::token_author_dict`] so they don't collide, which
+/// lets `train`/`classify` mix feature families freely.
+pub struct FeatureConfig {
+    /// Sizes (in chars, counted over the raw sentence including spaces/punctuation) of character
+    /// n-grams to additionally extract. Empty disables character n-grams.
+    pub char_ngram_sizes: Vec<usize>,
+    /// Whether to additionally extract word bigrams `w[i] w[i+1]` over the segmented word
+    /// tokens, mirroring the `word-bigram` features `w[-1]w[0], w[0]w[1]` used in the ltp
+    /// perceptron.
+    pub word_bigrams: bool,
+    /// Whether to additionally extract learned byte-pair-encoding subword units over the
+    /// segmented word tokens. The merge list is learned once at training time (if not already
+    /// populated) and stored in the config so classification re-applies the same segmentation.
+    /// See [`crate::model::BpeConfig`].
+    pub bpe: Option<crate::model::BpeConfig>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 /// A smoothed naive bayes model for Stylish Analysis
 pub struct BayesianModel {
@@ -49,8 +103,45 @@ pub struct BayesianModel {
     author_token_count: Vec<u32>,
     /// Total token count used to train the model
     total_token_count: u32,
+    /// How per-token evidence is combined into a per-author rating
+    scoring_mode: ScoringMode,
+    /// Which feature families `tokenize` extracts; must match between training and classification
+    feature_config: FeatureConfig,
+    /// How word tokens are filtered before counting; must match between training and
+    /// classification, see [`TokenPipeline`]
+    pipeline: TokenPipeline,
+    /// How strongly a fuzzy (non-exact) vocabulary match counts towards a rating, relative to an
+    /// exact match. `1.0` treats fuzzy and exact hits identically; `0.0` turns every fuzzy hit
+    /// into a neutral `0.5` rating for all authors.
+    fuzzy_discount: f32,
+    /// Trie over [`Self::token_author_dict`]'s keys, rebuilt after training/loading, used to
+    /// rescue tokens absent from `token_author_dict` that are a close edit distance away from a
+    /// known token. Not serialized; cheap to rebuild from `token_author_dict`.
+    #[serde(skip)]
+    fuzzy_trie: DynTrie<Vec<u32>>,
+    /// Per-feature-family (the `w:`/`b:`/`c{n}:`/`p:` namespace prefix from
+    /// [`Self::tokenize`]) vocabulary size and per-author token totals, rebuilt after
+    /// training/loading. [`Self::predicate_mnb`]/[`Self::predicate_mdl`] smooth each family
+    /// against its own `V` and per-author denominator rather than the combined dictionary's,
+    /// since mixing families (e.g. the much higher per-sentence yield of character n-grams)
+    /// into one global vocabulary/denominator would let one family swamp the smoothing for all
+    /// the others. Not serialized; cheap to rebuild from `token_author_dict`.
+    #[serde(skip)]
+    family_stats: HashMap<String, FamilyStats>,
+}
+
+/// Per-feature-family vocabulary size and per-author token totals; see
+/// [`BayesianModel::family_stats`].
+#[derive(Debug, Default, Clone)]
+struct FamilyStats {
+    vocab_size: usize,
+    author_token_count: Vec<u32>,
 }
 
+/// Default discount applied to fuzzy (non-exact) vocabulary matches; see
+/// [`BayesianModel::fuzzy_discount`].
+const DEFAULT_FUZZY_DISCOUNT: f32 = 0.5;
+
 const MAX_SENTENCE_LENGTH: usize = 96;
 
 /// Rating of un-seen token
@@ -64,6 +155,25 @@ impl BayesianModel {
     /// Train the bayesian model using given dataset. The dataset consists of `String` pairs, where
     /// the first is author, and the second is path to the text file. Must be `.txt` format of pure text.
     pub fn train(dataset: Vec<(String, String)>) -> io::Result<Self> {
+        Self::train_with_features(dataset, FeatureConfig::default())
+    }
+
+    /// Train the bayesian model, extracting the feature families selected by `feature_config` in
+    /// addition to word unigrams. See [`FeatureConfig`].
+    pub fn train_with_features(
+        dataset: Vec<(String, String)>,
+        feature_config: FeatureConfig,
+    ) -> io::Result<Self> {
+        Self::train_with_config(dataset, feature_config, TokenPipeline::default())
+    }
+
+    /// Train the bayesian model with full control over feature extraction and token filtering.
+    /// See [`FeatureConfig`] and [`TokenPipeline`].
+    pub fn train_with_config(
+        dataset: Vec<(String, String)>,
+        feature_config: FeatureConfig,
+        pipeline: TokenPipeline,
+    ) -> io::Result<Self> {
         log::trace!("Find all authors.");
         let author_dict = dataset
             .iter()
@@ -73,16 +183,26 @@ impl BayesianModel {
         let author_count = authors.len();
         log::trace!("Contains {} authors in total.", author_count);
 
+        log::trace!("Read all documents.");
+        let documents = dataset
+            .into_iter()
+            .map(|(author, path)| {
+                log::trace!("Reading: ('{}', `{}`)", author, path);
+                let text = std::fs::read_to_string(&path)?;
+                Ok((author, text))
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let feature_config = Self::learn_bpe_merges_if_configured(&documents, feature_config, &pipeline);
+
+
         // Find all words and their count in each author's texts
         let mut token_author_dict: HashMap<String, Vec<u32>> = HashMap::new();
-        for (author, path) in dataset {
-            log::trace!("Indexing: ('{}', `{}`)", author, path);
+        for (author, text) in &documents {
             let author_index = authors.iter().position(|name| author.eq(name)).unwrap();
 
-            let text = std::fs::read_to_string(path)?;
-            let tokens = Self::tokenize(&text);
+            let tokens = Self::tokenize(text, &feature_config, &pipeline);
             for token in tokens {
-                let token = token.to_string();
                 let token_count = token_author_dict
                     .entry(token)
                     .or_insert(vec![0; author_count]);
@@ -101,37 +221,286 @@ impl BayesianModel {
                 });
         let total_token_count = author_token_count.iter().sum::<u32>();
         log::trace!("Contains {} tokens in total.", total_token_count);
+        let fuzzy_trie = Self::build_fuzzy_trie(&token_author_dict);
+        let family_stats = Self::build_family_stats(&token_author_dict, author_count);
         Ok(Self {
             authors,
             author_token_count,
             token_author_dict,
             total_token_count,
+            scoring_mode: ScoringMode::default(),
+            feature_config,
+            pipeline,
+            fuzzy_discount: DEFAULT_FUZZY_DISCOUNT,
+            fuzzy_trie,
+            family_stats,
         })
     }
 
+    /// If `feature_config.bpe` is configured and hasn't learned its merges yet, learns them from
+    /// `documents` via [`crate::model::bpe::learn_merges`] and returns a config with `merges`
+    /// populated. Leaves an already-populated merge list untouched, so re-training with a
+    /// previously-learned config (e.g. incremental training) reuses the same segmentation.
+    /// `pub(crate)` so [`crate::model::PerceptronModel`] can share it.
+    pub(crate) fn learn_bpe_merges_if_configured(
+        documents: &[(String, String)],
+        mut feature_config: FeatureConfig,
+        pipeline: &TokenPipeline,
+    ) -> FeatureConfig {
+        if let Some(bpe_config) = &mut feature_config.bpe {
+            if bpe_config.merges.is_empty() {
+                log::trace!("Learning BPE merges.");
+                bpe_config.merges = bpe::learn_merges(
+                    documents.iter().map(|(_, text)| text.as_str()),
+                    bpe_config.vocab_size,
+                    bpe_config.min_frequency,
+                    pipeline,
+                );
+            }
+        }
+        feature_config
+    }
+
+    /// Selects the scoring mode used by [`Self::predicate`]. Defaults to [`ScoringMode::Fisher`].
+    pub fn set_scoring_mode(&mut self, scoring_mode: ScoringMode) {
+        self.scoring_mode = scoring_mode;
+    }
+
+    /// Sets how strongly a fuzzy (non-exact) vocabulary match counts towards a rating, relative
+    /// to an exact match. Defaults to [`DEFAULT_FUZZY_DISCOUNT`]. See [`Self::fuzzy_discount`].
+    pub fn set_fuzzy_discount(&mut self, fuzzy_discount: f32) {
+        self.fuzzy_discount = fuzzy_discount;
+    }
+
+    /// Folds one more training document for `author` into this model in place, instead of
+    /// rebuilding from a full dataset via [`Self::train_with_config`]. If `author` hasn't been
+    /// seen before, they're appended to [`Self::authors`] and every existing count vector in
+    /// [`Self::token_author_dict`] grows a new zero slot for them, keeping the vectors aligned
+    /// with [`Self::authors`].
+    ///
+    /// `text` is tokenized with this model's stored `feature_config`/`pipeline`, so a BPE feature
+    /// family keeps using the merges learned at the original training time.
+    pub fn update(&mut self, author: &str, text: &str) {
+        let author_index = match self.authors.iter().position(|name| author == name) {
+            Some(index) => index,
+            None => {
+                let index = self.authors.len();
+                self.authors.push(author.to_string());
+                self.author_token_count.push(0);
+                for counts in self.token_author_dict.values_mut() {
+                    counts.push(0);
+                }
+                index
+            }
+        };
+
+        let author_count = self.authors.len();
+        let tokens = Self::tokenize(text, &self.feature_config, &self.pipeline);
+        for token in tokens {
+            let counts = self
+                .token_author_dict
+                .entry(token)
+                .or_insert_with(|| vec![0; author_count]);
+            counts[author_index] += 1;
+            self.author_token_count[author_index] += 1;
+            self.total_token_count += 1;
+        }
+
+        self.fuzzy_trie = Self::build_fuzzy_trie(&self.token_author_dict);
+        self.family_stats = Self::build_family_stats(&self.token_author_dict, self.authors.len());
+    }
+
+    /// Merges `other`'s authors and token counts into this model in place, so large corpora can
+    /// be indexed as independent shards (e.g. in parallel) and combined afterwards instead of
+    /// re-tokenizing everything in one pass. Authors are unioned by name; an author present in
+    /// both models has its counts summed rather than duplicated. `other`'s per-token count
+    /// vectors are re-indexed into this model's (possibly wider, after the union) author order
+    /// before summing, since the two models were trained over different author sets.
+    ///
+    /// `other`'s `feature_config`/`pipeline` are assumed to match this model's; merging models
+    /// trained with different feature families or token filtering produces a model whose tokens
+    /// are a meaningless mix of both.
+    pub fn merge(&mut self, other: &BayesianModel) {
+        let other_author_indices = other
+            .authors
+            .iter()
+            .map(|name| {
+                match self.authors.iter().position(|existing| existing == name) {
+                    Some(index) => index,
+                    None => {
+                        let index = self.authors.len();
+                        self.authors.push(name.clone());
+                        self.author_token_count.push(0);
+                        for counts in self.token_author_dict.values_mut() {
+                            counts.push(0);
+                        }
+                        index
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let author_count = self.authors.len();
+        for (token, other_counts) in &other.token_author_dict {
+            let counts = self
+                .token_author_dict
+                .entry(token.clone())
+                .or_insert_with(|| vec![0; author_count]);
+            for (other_index, &count) in other_counts.iter().enumerate() {
+                counts[other_author_indices[other_index]] += count;
+            }
+        }
+
+        for (other_index, &count) in other.author_token_count.iter().enumerate() {
+            self.author_token_count[other_author_indices[other_index]] += count;
+        }
+        self.total_token_count += other.total_token_count;
+
+        self.fuzzy_trie = Self::build_fuzzy_trie(&self.token_author_dict);
+        self.family_stats = Self::build_family_stats(&self.token_author_dict, self.authors.len());
+    }
+
+    fn build_fuzzy_trie(token_author_dict: &HashMap<String, Vec<u32>>) -> DynTrie<Vec<u32>> {
+        let mut trie = DynTrie::default();
+        for (token, counts) in token_author_dict {
+            trie.insert(token, counts.clone());
+        }
+        trie
+    }
+
+    /// The namespace prefix (`w`, `b`, `c{n}`, `p`) [`Self::tokenize`] prepends before the `:` to
+    /// keep feature families from colliding in [`Self::token_author_dict`]; used to key
+    /// [`Self::family_stats`] so each family is smoothed against its own vocabulary.
+    fn feature_family(token: &str) -> &str {
+        token.split_once(':').map_or(token, |(family, _)| family)
+    }
+
+    fn build_family_stats(
+        token_author_dict: &HashMap<String, Vec<u32>>,
+        author_count: usize,
+    ) -> HashMap<String, FamilyStats> {
+        let mut stats: HashMap<String, FamilyStats> = HashMap::new();
+        for (token, counts) in token_author_dict {
+            let family_stats = stats
+                .entry(Self::feature_family(token).to_string())
+                .or_insert_with(|| FamilyStats {
+                    vocab_size: 0,
+                    author_token_count: vec![0; author_count],
+                });
+            family_stats.vocab_size += 1;
+            for (author, &count) in counts.iter().enumerate() {
+                family_stats.author_token_count[author] += count;
+            }
+        }
+        stats
+    }
+
+    /// The largest edit distance a token may be from a known token to fuzzily match, scaled by token
+    /// length so short tokens (where one typo is proportionally huge) stay strict.
+    fn fuzzy_edit_bound(token: &str) -> usize {
+        (token.chars().count() / 4).max(1)
+    }
+
+    /// The feature families extracted at training time, which classification must match. See
+    /// [`FeatureConfig`].
+    pub fn feature_config(&self) -> &FeatureConfig {
+        &self.feature_config
+    }
+
+    /// Save the model to `path`, prefixed with a magic tag and format version (see
+    /// [`crate::model::persist`]) so that [`Self::load`] can reject models saved by an
+    /// incompatible build instead of silently misreading them.
     pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), BayesianSaveError> {
         fsio::file::ensure_exists(&path.as_ref())?;
-        let bin_vec = postcard::to_allocvec(self)?;
+        let bin_vec = persist::encode(persist::ModelKind::Bayesian, self)?;
         std::fs::write(path, bin_vec)?;
         Ok(())
     }
 
     pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, BayesianLoadError> {
         let bin_vec = std::fs::read(path)?;
-        let model = postcard::from_bytes(&bin_vec)?;
+        let mut model: Self = persist::decode(persist::ModelKind::Bayesian, &bin_vec)?;
+        // `fuzzy_trie`/`family_stats` are `#[serde(skip)]`'d, since they're cheap to rebuild from
+        // `token_author_dict`.
+        model.fuzzy_trie = Self::build_fuzzy_trie(&model.token_author_dict);
+        model.family_stats = Self::build_family_stats(&model.token_author_dict, model.authors.len());
         Ok(model)
     }
 
-    pub fn preprocess(text: &str) -> Vec<(usize, Vec<&str>)> {
+    pub fn preprocess(
+        text: &str,
+        feature_config: &FeatureConfig,
+        pipeline: &TokenPipeline,
+    ) -> Vec<(usize, Vec<String>)> {
+        Self::split_sentences(text)
+            .into_iter()
+            .map(|(index, sentence)| {
+                (index, Self::tokenize(&sentence, feature_config, pipeline))
+            })
+            .collect::<Vec<_>>()
+    }
+
+    /// Splits `text` into sentence-sized chunks without tokenizing them, for consumers (like
+    /// [`crate::model::TransformerModel`]) that need the raw sentence text rather than this
+    /// model's namespaced feature tokens.
+    pub(crate) fn split_sentences(text: &str) -> Vec<(usize, String)> {
         let ts = TextSplitter::new(MAX_SENTENCE_LENGTH);
         ts.chunk_indices(text)
-            .map(|(index, sentence)| (index, Self::tokenize(sentence)))
+            .map(|(index, sentence)| (index, sentence.to_string()))
             .collect::<Vec<_>>()
     }
 
-    pub fn tokenize(text: &str) -> Vec<&str> {
-        // TODO: Remove punctuations and meaningless words
-        text.segment_str().collect::<Vec<_>>()
+    /// Extracts the feature families selected by `feature_config` from `text`, after routing word
+    /// tokens through `pipeline` (punctuation/stopword filtering, case folding, stemming). Word
+    /// unigrams are always extracted; character n-grams, word bigrams and BPE subwords are
+    /// namespaced with a `c{n}:`/`b:`/`p:` prefix respectively (word unigrams use `w:`) so the
+    /// feature families never collide in [`Self::token_author_dict`]. Character n-grams are
+    /// extracted from the raw text, not the filtered words, since they're meant to capture
+    /// punctuation habits.
+    pub fn tokenize(
+        text: &str,
+        feature_config: &FeatureConfig,
+        pipeline: &TokenPipeline,
+    ) -> Vec<String> {
+        let words = text
+            .segment_str()
+            .filter_map(|word| pipeline.apply(word))
+            .collect::<Vec<_>>();
+
+        let mut features = Vec::with_capacity(words.len());
+        features.extend(words.iter().map(|word| format!("w:{word}")));
+
+        if feature_config.word_bigrams {
+            features.extend(
+                words
+                    .windows(2)
+                    .map(|pair| format!("b:{} {}", pair[0], pair[1])),
+            );
+        }
+
+        if let Some(bpe_config) = &feature_config.bpe {
+            features.extend(words.iter().flat_map(|word| {
+                bpe::apply_merges(word, &bpe_config.merges)
+                    .into_iter()
+                    .map(|unit| format!("p:{unit}"))
+            }));
+        }
+
+        if !feature_config.char_ngram_sizes.is_empty() {
+            let chars = text.chars().collect::<Vec<_>>();
+            for &n in &feature_config.char_ngram_sizes {
+                if n == 0 || chars.len() < n {
+                    continue;
+                }
+                features.extend(
+                    chars
+                        .windows(n)
+                        .map(|window| format!("c{n}:{}", window.iter().collect::<String>())),
+                );
+            }
+        }
+
+        features
     }
 
     pub fn classify_text(&self, text: &str) -> Classification {
@@ -157,12 +526,72 @@ impl BayesianModel {
         }
     }
 
+    /// Beam-searches the `k` most probable full author-assignment sequences across this
+    /// document's sentences, instead of blending per-sentence distributions into one average.
+    /// See [`crate::model::beam_search_top_k`].
+    pub fn classify_text_top_k(&self, text: &str, k: usize) -> Vec<(Vec<String>, f64)> {
+        let predication = self.predicate_text(text);
+        crate::model::beam_search_top_k(&predication, k)
+            .into_iter()
+            .map(|(outcomes, log_prob)| {
+                (
+                    outcomes
+                        .into_iter()
+                        .map(|author| self.authors[author].clone())
+                        .collect(),
+                    log_prob,
+                )
+            })
+            .collect()
+    }
+
+    /// Scores authors by compression distance (minimum description length) instead of
+    /// [`Self::scoring_mode`]'s Fisher/MNB combiners: the predicted author is the one whose
+    /// per-token probability model needs the fewest bits to encode the unknown text, which avoids
+    /// the Fisher combiner's hard [`MIN_RATING`]/[`MAX_RATING`] clamps. See
+    /// [`Self::classify_text_mdl`].
+    pub fn predicate_text_mdl(&self, text: &str) -> Predication {
+        self.predicate_text_with(text, |tokens| self.predicate_mdl(tokens))
+    }
+
+    /// Like [`Self::classify_text`], but scores authors by compression distance via
+    /// [`Self::predicate_text_mdl`] rather than [`Self::scoring_mode`]'s Fisher/MNB combiners.
+    pub fn classify_text_mdl(&self, text: &str) -> Classification {
+        let transform = |v: Vec<f32>| {
+            v.into_iter()
+                .enumerate()
+                .map(|(author, probability)| (self.authors[author].clone(), probability))
+                .collect()
+        };
+        let Predication {
+            sentences_predicate,
+            total_predicate,
+        } = self.predicate_text_mdl(text);
+        let sentences_classification = sentences_predicate
+            .into_iter()
+            .map(|(sentence_index, predication)| (sentence_index, transform(predication)))
+            .collect::<Vec<_>>();
+        let total_classification = transform(total_predicate);
+        Classification {
+            sentences_classification,
+            total_classification,
+        }
+    }
+
     fn predicate_text(&self, text: &str) -> Predication {
-        let sentences = Self::preprocess(text);
+        self.predicate_text_with(text, |tokens| self.predicate(tokens))
+    }
+
+    fn predicate_text_with(
+        &self,
+        text: &str,
+        predicate: impl Fn(&[String]) -> Vec<f32>,
+    ) -> Predication {
+        let sentences = Self::preprocess(text, &self.feature_config, &self.pipeline);
         let sentence_count = sentences.len();
         let sentences_predicate = sentences
             .into_iter()
-            .map(|(sentence_index, sentence)| (sentence_index, self.predicate(&sentence)))
+            .map(|(sentence_index, sentence)| (sentence_index, predicate(&sentence)))
             .collect::<Vec<_>>();
         let author_count = self.authors.len();
         let total_predicate = sentences_predicate
@@ -185,13 +614,109 @@ impl BayesianModel {
         }
     }
 
-    fn predicate(&self, tokens: &Vec<&str>) -> Vec<f32> {
+    fn predicate(&self, tokens: &[String]) -> Vec<f32> {
+        match self.scoring_mode {
+            ScoringMode::Fisher => self.predicate_fisher(tokens),
+            ScoringMode::MultinomialNaiveBayes => self.predicate_mnb(tokens),
+        }
+    }
+
+    /// Multinomial naive Bayes scoring in log-space with Laplace smoothing, as described on
+    /// [`ScoringMode::MultinomialNaiveBayes`]. Each token's `V`/per-author denominator are its own
+    /// feature family's (see [`Self::family_stats`]), not the combined dictionary's, so a
+    /// high-yield family like character n-grams can't swamp the smoothing for the others.
+    fn predicate_mnb(&self, tokens: &[String]) -> Vec<f32> {
+        let author_count = self.authors.len();
+
+        let mut token_freq: HashMap<&str, u32> = HashMap::new();
+        for token in tokens {
+            *token_freq.entry(token.as_str()).or_insert(0) += 1;
+        }
+
+        let scores = (0..author_count)
+            .map(|author| {
+                let prior = (self.author_token_count[author] as f64
+                    / self.total_token_count as f64)
+                    .ln();
+                let log_likelihood = token_freq
+                    .iter()
+                    .map(|(token, freq)| {
+                        let family_stats = self.family_stats.get(Self::feature_family(token));
+                        let vocab_size =
+                            family_stats.map(|stats| stats.vocab_size).unwrap_or(0) as f64;
+                        let family_author_token_count = family_stats
+                            .map(|stats| stats.author_token_count[author])
+                            .unwrap_or(0) as f64;
+                        let token_author_count = self
+                            .token_author_dict
+                            .get(*token)
+                            .map(|counts| counts[author])
+                            .unwrap_or(0) as f64;
+                        *freq as f64
+                            * ((token_author_count + 1.0)
+                                / (family_author_token_count + vocab_size))
+                                .ln()
+                    })
+                    .sum::<f64>();
+                prior + log_likelihood
+            })
+            .collect::<Vec<_>>();
+
+        softmax(&scores).into_iter().map(|p| p as f32).collect()
+    }
+
+    /// Compression-distance (MDL) scoring: builds a smoothed per-token probability model for each
+    /// author, `P_author(token) = (count[author] + 1) / (family_author_token_count[author] + V)`
+    /// (`V`/the denominator scoped to the token's own feature family, like
+    /// [`Self::predicate_mnb`]), then computes the text's cross-entropy code length under that
+    /// author, `L_author = -Σ_token log2 P_author(token)` (unseen tokens fall through the same
+    /// additive smoothing as an escape code). The predicted author minimizes `L_author`;
+    /// softmaxing over `-L_author` turns the code lengths into a posterior.
+    fn predicate_mdl(&self, tokens: &[String]) -> Vec<f32> {
+        let author_count = self.authors.len();
+
+        let mut token_freq: HashMap<&str, u32> = HashMap::new();
+        for token in tokens {
+            *token_freq.entry(token.as_str()).or_insert(0) += 1;
+        }
+
+        let negated_code_lengths = (0..author_count)
+            .map(|author| {
+                let code_length = token_freq
+                    .iter()
+                    .map(|(token, freq)| {
+                        let family_stats = self.family_stats.get(Self::feature_family(token));
+                        let vocab_size =
+                            family_stats.map(|stats| stats.vocab_size).unwrap_or(0) as f64;
+                        let family_author_token_count = family_stats
+                            .map(|stats| stats.author_token_count[author])
+                            .unwrap_or(0) as f64;
+                        let token_author_count = self
+                            .token_author_dict
+                            .get(*token)
+                            .map(|counts| counts[author])
+                            .unwrap_or(0) as f64;
+                        let probability = (token_author_count + 1.0)
+                            / (family_author_token_count + vocab_size);
+                        -(*freq as f64) * probability.log2()
+                    })
+                    .sum::<f64>();
+                -code_length
+            })
+            .collect::<Vec<_>>();
+
+        softmax(&negated_code_lengths)
+            .into_iter()
+            .map(|p| p as f32)
+            .collect()
+    }
+
+    fn predicate_fisher(&self, tokens: &[String]) -> Vec<f32> {
         let author_count = self.authors.len();
         let mut ratings = vec![vec![]; author_count];
 
         for token in tokens {
-            let token = token.to_string();
-            if let Some(token_count) = self.token_author_dict.get(&token) {
+            if let Some(token_count) = self.token_author_dict.get(token) {
                 let count = token_count.iter().sum::<u32>();
                 for author in 0..author_count {
                     let token_author_count = token_count[author];
@@ -209,6 +734,23 @@ impl BayesianModel {
                         .min(MAX_RATING);
                     ratings[author].push(rating)
                 }
+            } else if let Some((_, token_count, _distance)) = self
+                .fuzzy_trie
+                .fuzzy_get(token, Self::fuzzy_edit_bound(token))
+            {
+                let count = token_count.iter().sum::<u32>();
+                for author in 0..author_count {
+                    let token_author_count = token_count[author];
+                    let this_probability =
+                        token_author_count as f32 / self.author_token_count[author] as f32;
+                    let other_probability = (count - token_author_count) as f32
+                        / (self.total_token_count - self.author_token_count[author]) as f32;
+                    let rating = (this_probability / (this_probability + other_probability))
+                        .max(MIN_RATING)
+                        .min(MAX_RATING);
+                    // Fuzzy hits count less than exact ones: shrink the rating towards neutral.
+                    ratings[author].push(0.5 + (rating - 0.5) * self.fuzzy_discount);
+                }
             } else {
                 for rating in ratings.iter_mut() {
                     rating.push(MIN_RATING)
@@ -250,6 +792,15 @@ impl BayesianModel {
     }
 }
 
+/// Numerically safe softmax: subtracts the max score before exponentiating so large log-scores
+/// don't overflow `f64::exp`.
+pub(crate) fn softmax(scores: &[f64]) -> Vec<f64> {
+    let max = scores.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let exps = scores.iter().map(|score| (score - max).exp()).collect::<Vec<_>>();
+    let sum = exps.iter().sum::<f64>();
+    exps.into_iter().map(|exp| exp / sum).collect()
+}
+
 impl Display for BayesianModel {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -260,3 +811,148 @@ impl Display for BayesianModel {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes `text` to a uniquely named file under the OS temp dir and returns its path, for
+    /// tests that need a real file to hand to [`BayesianModel::train`]'s `(author, path)` dataset
+    /// shape.
+    fn write_temp_doc(name: &str, text: &str) -> String {
+        let path =
+            std::env::temp_dir().join(format!("stal-test-{}-{name}.txt", std::process::id()));
+        std::fs::write(&path, text).unwrap();
+        path.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn train_classify_round_trip_with_mixed_feature_families() {
+        let alice = write_temp_doc(
+            "alice",
+            "the quick brown fox jumps over the lazy dog the fox runs across the yard",
+        );
+        let bob = write_temp_doc(
+            "bob",
+            "graphs have vertices and edges a directed acyclic graph has no cycles between nodes",
+        );
+        let dataset = vec![("alice".to_string(), alice.clone()), ("bob".to_string(), bob.clone())];
+        let feature_config = FeatureConfig {
+            char_ngram_sizes: vec![3],
+            word_bigrams: true,
+            bpe: None,
+        };
+
+        let mut model = BayesianModel::train_with_features(dataset, feature_config).unwrap();
+        model.set_scoring_mode(ScoringMode::MultinomialNaiveBayes);
+
+        let classification = model.classify_text("the quick fox jumps over the lazy dog");
+        let (top_author, _) = classification
+            .total_classification
+            .iter()
+            .max_by(|a, b| a.1.partial_cmp(b.1).unwrap())
+            .unwrap();
+        assert_eq!(top_author, "alice");
+
+        std::fs::remove_file(alice).ok();
+        std::fs::remove_file(bob).ok();
+    }
+
+    #[test]
+    fn mdl_scorer_picks_the_lower_code_length_author() {
+        let alice = write_temp_doc(
+            "mdl-alice",
+            "the quick brown fox jumps over the lazy dog the fox runs across the yard",
+        );
+        let bob = write_temp_doc(
+            "mdl-bob",
+            "graphs have vertices and edges a directed acyclic graph has no cycles between nodes",
+        );
+        let dataset = vec![("alice".to_string(), alice.clone()), ("bob".to_string(), bob.clone())];
+
+        let model = BayesianModel::train(dataset).unwrap();
+        let classification = model.classify_text_mdl("the quick fox jumps over the lazy dog");
+        let (top_author, _) = classification
+            .total_classification
+            .iter()
+            .max_by(|a, b| a.1.partial_cmp(b.1).unwrap())
+            .unwrap();
+        assert_eq!(top_author, "alice");
+
+        std::fs::remove_file(alice).ok();
+        std::fs::remove_file(bob).ok();
+    }
+
+    #[test]
+    fn fuzzy_matching_recovers_a_misspelled_token() {
+        let alice = write_temp_doc(
+            "fuzzy-alice",
+            "the quick brown fox jumps over the lazy dog the fox runs across the yard",
+        );
+        let bob = write_temp_doc(
+            "fuzzy-bob",
+            "graphs have vertices and edges a directed acyclic graph has no cycles between nodes",
+        );
+        let dataset = vec![("alice".to_string(), alice.clone()), ("bob".to_string(), bob.clone())];
+
+        let model = BayesianModel::train(dataset).unwrap();
+        // "jumpz" isn't in either author's vocabulary, but is one edit away from "jumps", which
+        // only appears in alice's document; the default Fisher scoring mode's fuzzy trie lookup
+        // (`fuzzy_edit_bound`) should still surface that signal instead of treating it as a flat
+        // unknown token shared by neither author.
+        let classification = model.classify_text("the quick fox jumpz over the lazy dog");
+        let (top_author, _) = classification
+            .total_classification
+            .iter()
+            .max_by(|a, b| a.1.partial_cmp(b.1).unwrap())
+            .unwrap();
+        assert_eq!(top_author, "alice");
+
+        std::fs::remove_file(alice).ok();
+        std::fs::remove_file(bob).ok();
+    }
+
+    #[test]
+    fn update_folds_a_new_document_into_an_existing_author() {
+        let alice = write_temp_doc("update-alice", "the quick brown fox jumps over the lazy dog");
+        let dataset = vec![("alice".to_string(), alice.clone())];
+
+        let mut model = BayesianModel::train(dataset).unwrap();
+        let before = model.token_author_dict.get("w:fence").cloned();
+        assert!(before.is_none());
+
+        model.update("alice", "the fox leaps the fence");
+
+        let after_author_count = model.authors.len();
+        assert_eq!(after_author_count, 1);
+        let fence_counts = model
+            .token_author_dict
+            .get("w:fence")
+            .expect("update() should have inserted the new token");
+        assert_eq!(fence_counts[0], 1);
+
+        std::fs::remove_file(alice).ok();
+    }
+
+    #[test]
+    fn merge_unions_authors_and_sums_shared_token_counts() {
+        let alice = write_temp_doc("merge-alice", "the quick brown fox jumps over the lazy dog");
+        let bob = write_temp_doc("merge-bob", "the quick fox runs across the yard");
+
+        let mut alice_model = BayesianModel::train(vec![("alice".to_string(), alice.clone())]).unwrap();
+        let bob_model = BayesianModel::train(vec![("bob".to_string(), bob.clone())]).unwrap();
+
+        alice_model.merge(&bob_model);
+
+        assert_eq!(alice_model.authors.len(), 2);
+        let bob_index = alice_model.authors.iter().position(|name| name == "bob").unwrap();
+        let the_counts = alice_model
+            .token_author_dict
+            .get("w:the")
+            .expect("\"the\" is shared by both documents");
+        assert_eq!(the_counts[bob_index], 2);
+
+        std::fs::remove_file(alice).ok();
+        std::fs::remove_file(bob).ok();
+    }
+}
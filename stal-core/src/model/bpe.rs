@@ -0,0 +1,143 @@
+//! Byte-pair-encoding subword segmentation, offered as a feature family alongside word unigrams
+//! and character n-grams (see [`crate::model::FeatureConfig`]) so models can pick up sub-word
+//! stylistic signal (affixes, punctuation habits) that whole-word counts miss.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::TokenPipeline;
+
+/// Configures BPE subword segmentation. `merges` starts empty and is learned once, by
+/// [`learn_merges`], the first time a model is trained with this feature family enabled; it's
+/// then stored and serialized alongside the rest of the model so classification re-applies the
+/// exact same segmentation used at training time.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BpeConfig {
+    /// Target subword vocabulary size (starting symbols plus learned merges). Merging stops once
+    /// this is reached or no remaining pair reaches `min_frequency`.
+    pub vocab_size: usize,
+    /// Minimum adjacent-symbol-pair frequency required to keep merging.
+    pub min_frequency: u32,
+    /// The learned ordered merge list. Empty until [`learn_merges`] has populated it.
+    pub merges: Vec<(String, String)>,
+}
+
+/// Learns an ordered BPE merge list from `texts`: starting from the character alphabet, counts
+/// all adjacent symbol pairs across the corpus and iteratively merges the most frequent pair into
+/// a new symbol, until `vocab_size` is reached or no remaining pair meets `min_frequency`. Words
+/// are extracted and filtered the same way as word unigrams, via `pipeline`, so the learned
+/// vocabulary matches what `predicate` will see at classification time.
+pub(crate) fn learn_merges<'a>(
+    texts: impl Iterator<Item = &'a str>,
+    vocab_size: usize,
+    min_frequency: u32,
+    pipeline: &TokenPipeline,
+) -> Vec<(String, String)> {
+    use charabia::Segment;
+
+    let mut word_freqs: HashMap<Vec<String>, u32> = HashMap::new();
+    for text in texts {
+        for word in text.segment_str().filter_map(|word| pipeline.apply(word)) {
+            let symbols = word.chars().map(|ch| ch.to_string()).collect::<Vec<_>>();
+            *word_freqs.entry(symbols).or_insert(0) += 1;
+        }
+    }
+
+    let mut vocab = word_freqs
+        .keys()
+        .flatten()
+        .cloned()
+        .collect::<HashSet<_>>();
+    let mut merges = Vec::new();
+
+    while vocab.len() < vocab_size {
+        let mut pair_counts: HashMap<(String, String), u32> = HashMap::new();
+        for (symbols, freq) in &word_freqs {
+            for pair in symbols.windows(2) {
+                *pair_counts
+                    .entry((pair[0].clone(), pair[1].clone()))
+                    .or_insert(0) += freq;
+            }
+        }
+
+        // Break frequency ties on the pair itself (smallest pair wins), not `HashMap` order.
+        let Some((best_pair, best_count)) = pair_counts
+            .into_iter()
+            .max_by(|(pair_a, count_a), (pair_b, count_b)| {
+                count_a.cmp(count_b).then_with(|| pair_b.cmp(pair_a))
+            })
+        else {
+            break;
+        };
+        if best_count < min_frequency {
+            break;
+        }
+
+        let merged = format!("{}{}", best_pair.0, best_pair.1);
+        word_freqs = word_freqs
+            .into_iter()
+            .map(|(symbols, freq)| (merge_symbols(&symbols, &best_pair, &merged), freq))
+            .collect();
+        vocab.insert(merged);
+        merges.push(best_pair);
+    }
+
+    merges
+}
+
+/// Re-applies a previously learned merge list, in order, to segment `word` into the same subword
+/// units used at training time.
+pub(crate) fn apply_merges(word: &str, merges: &[(String, String)]) -> Vec<String> {
+    let mut symbols = word.chars().map(|ch| ch.to_string()).collect::<Vec<_>>();
+    for pair in merges {
+        let merged = format!("{}{}", pair.0, pair.1);
+        symbols = merge_symbols(&symbols, pair, &merged);
+    }
+    symbols
+}
+
+fn merge_symbols(symbols: &[String], pair: &(String, String), merged: &str) -> Vec<String> {
+    let mut result = Vec::with_capacity(symbols.len());
+    let mut index = 0;
+    while index < symbols.len() {
+        if index + 1 < symbols.len() && symbols[index] == pair.0 && symbols[index + 1] == pair.1 {
+            result.push(merged.to_string());
+            index += 2;
+        } else {
+            result.push(symbols[index].clone());
+            index += 1;
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn learn_merges_is_deterministic_across_runs() {
+        // The fifth merge ties ("aaaa", "aaaa") with ("aaaa", "aa") at frequency 2; ties are
+        // broken on the pair itself rather than `HashMap` iteration order, so both calls still
+        // learn the same merges.
+        let pipeline = TokenPipeline::default();
+        let texts = ["aaaaaaaaaa bbbbbbbbbb aaaaaaaaaa"];
+
+        let first = learn_merges(texts.iter().copied(), 8, 1, &pipeline);
+        let second = learn_merges(texts.iter().copied(), 8, 1, &pipeline);
+
+        assert!(!first.is_empty());
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn apply_merges_reproduces_the_training_time_segmentation() {
+        let pipeline = TokenPipeline::default();
+        let merges = learn_merges(["aaaaaaaaaa bbbbbbbbbb aaaaaaaaaa"].into_iter(), 8, 1, &pipeline);
+
+        let first = apply_merges("aaaaaaaaaa", &merges);
+        let second = apply_merges("aaaaaaaaaa", &merges);
+        assert_eq!(first, second);
+    }
+}
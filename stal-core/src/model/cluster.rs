@@ -0,0 +1,243 @@
+//! Unsupervised author clustering via the Gibbs Sampling Dirichlet Multinomial Mixture
+//! (movie-group-process), for corpora with no ground-truth author labels.
+
+use std::collections::{HashMap, HashSet};
+use std::io;
+
+use rand::Rng;
+
+use crate::model::bayesian::{BayesianModel, FeatureConfig};
+use crate::model::TokenPipeline;
+
+/// Result of [`cluster`]: a synthetic-labeled dataset usable directly as [`BayesianModel::train`]'s
+/// `dataset` argument, plus each surviving cluster's most frequent tokens for inspection.
+#[derive(Debug)]
+pub struct ClusterResult {
+    /// `(synthetic author label, path)` pairs, in the same shape [`BayesianModel::train`] expects.
+    pub dataset: Vec<(String, String)>,
+    /// For each surviving (non-empty) cluster, its tokens and raw occurrence counts, sorted
+    /// descending by count.
+    pub top_tokens: Vec<Vec<(String, u32)>>,
+}
+
+/// Discovers author groupings among unlabeled documents with the Gibbs Sampling Dirichlet
+/// Multinomial Mixture (movie-group-process): each document is a bag of its tokenized words,
+/// initially assigned to a uniformly random cluster in `0..k_max`; every iteration, each document
+/// is removed from its cluster and re-sampled proportional to cluster size (`alpha`-smoothed) and
+/// word-overlap likelihood (`beta`-smoothed), computed in log-space to avoid underflow. Empty
+/// clusters die out naturally, so the number of clusters in the returned [`ClusterResult`] is the
+/// inferred author count, which may be fewer than `k_max`.
+pub fn cluster(
+    paths: Vec<String>,
+    k_max: usize,
+    alpha: f32,
+    beta: f32,
+    iterations: usize,
+) -> io::Result<ClusterResult> {
+    if k_max == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "k_max must be at least 1",
+        ));
+    }
+
+    let feature_config = FeatureConfig::default();
+    let pipeline = TokenPipeline::default();
+
+    log::trace!("Tokenize {} documents for clustering.", paths.len());
+    let documents = paths
+        .iter()
+        .map(|path| {
+            let text = std::fs::read_to_string(path)?;
+            let tokens = BayesianModel::tokenize(&text, &feature_config, &pipeline);
+            let mut counts: HashMap<String, u32> = HashMap::new();
+            for token in tokens {
+                *counts.entry(token).or_insert(0) += 1;
+            }
+            Ok(counts)
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+
+    let doc_count = documents.len();
+    let vocab_size = documents
+        .iter()
+        .flat_map(|doc| doc.keys())
+        .collect::<HashSet<_>>()
+        .len() as f32;
+
+    let mut rng = rand::thread_rng();
+    let mut cluster_of = (0..doc_count)
+        .map(|_| rng.gen_range(0..k_max))
+        .collect::<Vec<_>>();
+
+    // Per-cluster aggregates: `m_z` = number of docs, `n_z` = total word occurrences, `n_z_w` =
+    // per-word occurrence counts.
+    let mut m_z = vec![0u32; k_max];
+    let mut n_z = vec![0u32; k_max];
+    let mut n_z_w: Vec<HashMap<String, u32>> = vec![HashMap::new(); k_max];
+
+    for (doc_index, doc) in documents.iter().enumerate() {
+        let cluster = cluster_of[doc_index];
+        m_z[cluster] += 1;
+        for (token, count) in doc {
+            n_z[cluster] += count;
+            *n_z_w[cluster].entry(token.clone()).or_insert(0) += count;
+        }
+    }
+
+    log::trace!("Run {} GSDMM iterations.", iterations);
+    for iteration in 0..iterations {
+        let mut moves = 0;
+        for (doc_index, doc) in documents.iter().enumerate() {
+            let doc_length = doc.values().sum::<u32>();
+            let current = cluster_of[doc_index];
+
+            // Remove the document from its current cluster before resampling.
+            m_z[current] -= 1;
+            n_z[current] -= doc_length;
+            for (token, count) in doc {
+                if let Some(entry) = n_z_w[current].get_mut(token) {
+                    *entry -= count;
+                }
+            }
+
+            let log_scores = (0..k_max)
+                .map(|cluster| {
+                    let cluster_prior = ((m_z[cluster] as f32 + alpha)
+                        / (doc_count as f32 - 1.0 + k_max as f32 * alpha))
+                        .ln();
+
+                    let word_log_prob = doc
+                        .iter()
+                        .map(|(token, &freq)| {
+                            let n_z_w_count = n_z_w[cluster].get(token).copied().unwrap_or(0);
+                            (0..freq)
+                                .map(|j| (n_z_w_count as f32 + beta + j as f32).ln())
+                                .sum::<f32>()
+                        })
+                        .sum::<f32>();
+                    let normalizer_log = (0..doc_length)
+                        .map(|i| (n_z[cluster] as f32 + vocab_size * beta + i as f32).ln())
+                        .sum::<f32>();
+
+                    cluster_prior + word_log_prob - normalizer_log
+                })
+                .collect::<Vec<_>>();
+
+            let new_cluster = sample_from_log_scores(&log_scores, &mut rng);
+            if new_cluster != current {
+                moves += 1;
+            }
+
+            // Reassign the document to its newly sampled cluster.
+            cluster_of[doc_index] = new_cluster;
+            m_z[new_cluster] += 1;
+            n_z[new_cluster] += doc_length;
+            for (token, count) in doc {
+                *n_z_w[new_cluster].entry(token.clone()).or_insert(0) += count;
+            }
+        }
+        log::trace!("Iteration {}: {} documents moved cluster.", iteration, moves);
+    }
+
+    let dataset = paths
+        .into_iter()
+        .zip(cluster_of.iter())
+        .map(|(path, &cluster)| (format!("cluster-{cluster}"), path))
+        .collect::<Vec<_>>();
+
+    let top_tokens = (0..k_max)
+        .filter(|&cluster| m_z[cluster] > 0)
+        .map(|cluster| {
+            let mut tokens = n_z_w[cluster]
+                .iter()
+                .map(|(token, &count)| (token.clone(), count))
+                .collect::<Vec<_>>();
+            tokens.sort_by(|a, b| b.1.cmp(&a.1));
+            tokens
+        })
+        .collect();
+
+    Ok(ClusterResult { dataset, top_tokens })
+}
+
+/// Exponentiates/normalizes log-space cluster scores and draws one cluster index from the
+/// resulting categorical distribution.
+fn sample_from_log_scores(log_scores: &[f32], rng: &mut impl Rng) -> usize {
+    let max = log_scores.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+    let weights = log_scores
+        .iter()
+        .map(|score| (score - max).exp())
+        .collect::<Vec<_>>();
+    let sum = weights.iter().sum::<f32>();
+
+    let mut threshold = rng.gen_range(0.0..sum);
+    for (index, &weight) in weights.iter().enumerate() {
+        if threshold < weight {
+            return index;
+        }
+        threshold -= weight;
+    }
+    weights.len() - 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_temp_doc(name: &str, text: &str) -> String {
+        let path =
+            std::env::temp_dir().join(format!("stal-cluster-test-{}-{name}.txt", std::process::id()));
+        std::fs::write(&path, text).unwrap();
+        path.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn rejects_zero_k_max() {
+        let result = cluster(vec![], 0, 0.1, 0.1, 1);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn gsdmm_converges_to_a_separate_cluster_per_topic() {
+        let fox_docs = (0..3)
+            .map(|index| {
+                write_temp_doc(
+                    &format!("fox-{index}"),
+                    "the quick brown fox jumps over the lazy dog the fox runs across the yard",
+                )
+            })
+            .collect::<Vec<_>>();
+        let graph_docs = (0..3)
+            .map(|index| {
+                write_temp_doc(
+                    &format!("graph-{index}"),
+                    "graphs have vertices and edges a directed acyclic graph has no cycles between nodes",
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let paths = fox_docs
+            .iter()
+            .chain(graph_docs.iter())
+            .cloned()
+            .collect::<Vec<_>>();
+        let result = cluster(paths, 4, 0.1, 0.1, 30).unwrap();
+
+        let fox_clusters = result.dataset[..3]
+            .iter()
+            .map(|(cluster, _)| cluster.clone())
+            .collect::<HashSet<_>>();
+        let graph_clusters = result.dataset[3..]
+            .iter()
+            .map(|(cluster, _)| cluster.clone())
+            .collect::<HashSet<_>>();
+        assert_eq!(fox_clusters.len(), 1, "fox documents should land in one cluster");
+        assert_eq!(graph_clusters.len(), 1, "graph documents should land in one cluster");
+        assert_ne!(fox_clusters, graph_clusters);
+
+        for path in fox_docs.into_iter().chain(graph_docs) {
+            std::fs::remove_file(path).ok();
+        }
+    }
+}
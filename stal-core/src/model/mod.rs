@@ -0,0 +1,141 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+pub(crate) mod bayesian;
+mod bpe;
+mod cluster;
+mod perceptron;
+mod pipeline;
+pub(crate) mod persist;
+#[cfg(feature = "transformer")]
+mod transformer;
+mod trie;
+
+pub use bayesian::*;
+pub use bpe::BpeConfig;
+pub use cluster::*;
+pub use perceptron::*;
+pub use pipeline::*;
+#[cfg(feature = "transformer")]
+pub use transformer::*;
+
+#[derive(Debug)]
+pub struct Predication {
+    pub sentences_predicate: Vec<(usize, Vec<f32>)>,
+    pub total_predicate: Vec<f32>,
+}
+
+#[derive(Debug)]
+pub struct Classification {
+    pub sentences_classification: Vec<(usize, HashMap<String, f32>)>,
+    pub total_classification: HashMap<String, f32>,
+}
+
+/// A candidate multi-sentence author-assignment sequence considered during beam search, ordered
+/// by cumulative log-probability. See [`beam_search_top_k`].
+#[derive(Debug, Clone, PartialEq)]
+struct Sequence {
+    outcomes: Vec<usize>,
+    log_prob: f64,
+}
+
+impl Eq for Sequence {}
+
+impl PartialOrd for Sequence {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Sequence {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Natural order on `log_prob`, so the `BinaryHeap` (a max-heap) pops the
+        // highest-probability candidate first; tie-break on outcomes for determinism.
+        self.log_prob
+            .partial_cmp(&other.log_prob)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| self.outcomes.cmp(&other.outcomes))
+    }
+}
+
+/// Beam search over a [`Predication`]'s per-sentence author-probability vectors, returning the
+/// `k` highest-scoring full label sequences (as author indices) and their cumulative
+/// log-probability.
+///
+/// Unlike averaging per-sentence distributions into one blended total, this surfaces mixed
+/// authorship (e.g. collaborative or plagiarized text) where different stretches of a document
+/// belong to different authors.
+pub fn beam_search_top_k(predication: &Predication, k: usize) -> Vec<(Vec<usize>, f64)> {
+    let mut beams = vec![Sequence {
+        outcomes: vec![],
+        log_prob: 0.0,
+    }];
+    for (_, sentence_probabilities) in &predication.sentences_predicate {
+        let mut candidates = BinaryHeap::new();
+        for beam in &beams {
+            for (author, &probability) in sentence_probabilities.iter().enumerate() {
+                let mut outcomes = beam.outcomes.clone();
+                outcomes.push(author);
+                let log_prob = beam.log_prob + (probability as f64).max(f64::MIN_POSITIVE).ln();
+                candidates.push(Sequence { outcomes, log_prob });
+            }
+        }
+        beams = std::iter::from_fn(|| candidates.pop()).take(k).collect();
+    }
+    beams.sort_by(|a, b| b.log_prob.partial_cmp(&a.log_prob).unwrap());
+    beams
+        .into_iter()
+        .map(|sequence| (sequence.outcomes, sequence.log_prob))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn returns_the_single_best_sequence_when_k_is_one() {
+        let predication = Predication {
+            sentences_predicate: vec![
+                (0, vec![0.9, 0.1]),
+                (1, vec![0.2, 0.8]),
+            ],
+            total_predicate: vec![],
+        };
+        let top = beam_search_top_k(&predication, 1);
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].0, vec![0, 1]);
+    }
+
+    #[test]
+    fn orders_top_k_sequences_by_descending_log_probability() {
+        let predication = Predication {
+            sentences_predicate: vec![
+                (0, vec![0.7, 0.3]),
+                (1, vec![0.6, 0.4]),
+            ],
+            total_predicate: vec![],
+        };
+        let top = beam_search_top_k(&predication, 4);
+        // 2 authors x 2 sentences = 4 possible sequences; all must be returned, sorted best-first.
+        assert_eq!(top.len(), 4);
+        assert_eq!(top[0].0, vec![0, 0]);
+        for window in top.windows(2) {
+            assert!(window[0].1 >= window[1].1);
+        }
+    }
+
+    #[test]
+    fn caps_beam_width_at_k() {
+        let predication = Predication {
+            sentences_predicate: vec![
+                (0, vec![0.5, 0.3, 0.2]),
+                (1, vec![0.5, 0.3, 0.2]),
+            ],
+            total_predicate: vec![],
+        };
+        let top = beam_search_top_k(&predication, 2);
+        assert_eq!(top.len(), 2);
+        assert_eq!(top[0].0, vec![0, 0]);
+    }
+}
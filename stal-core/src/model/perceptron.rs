@@ -0,0 +1,400 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::model::bayesian::{BayesianModel, FeatureConfig};
+use crate::model::persist::{self, VersionedLoadError};
+use crate::model::{Classification, Predication, TokenPipeline};
+
+#[derive(Debug, Error)]
+pub enum PerceptronSaveError {
+    #[error("Failed to serialize model.")]
+    Serialization(#[from] postcard::Error),
+    #[error("Failed to create file")]
+    File(#[from] fsio::error::FsIOError),
+    #[error("Failed to write model into file.")]
+    IO(#[from] io::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum PerceptronLoadError {
+    #[error("Not a stal model file (bad magic bytes).")]
+    BadMagic,
+    #[error(
+        "Model was saved by an incompatible format version ({found}); this build expects version {expected}."
+    )]
+    IncompatibleVersion { found: u16, expected: u16 },
+    #[error("File was saved as a different model kind; load it with the matching model type.")]
+    KindMismatch,
+    #[error("Failed to deserialize model.")]
+    Deserialization(#[from] postcard::Error),
+    #[error("Failed to read model from file.")]
+    IO(#[from] io::Error),
+}
+
+impl From<VersionedLoadError> for PerceptronLoadError {
+    fn from(error: VersionedLoadError) -> Self {
+        match error {
+            VersionedLoadError::BadMagic => PerceptronLoadError::BadMagic,
+            VersionedLoadError::IncompatibleVersion { found, expected } => {
+                PerceptronLoadError::IncompatibleVersion { found, expected }
+            }
+            VersionedLoadError::KindMismatch => PerceptronLoadError::KindMismatch,
+            VersionedLoadError::Deserialization(error) => {
+                PerceptronLoadError::Deserialization(error)
+            }
+        }
+    }
+}
+
+/// Maps features (tokens) to dense `u32` ids, growing as new features are seen during training.
+/// Mirrors vaporetto's `FeatureIDManager`.
+#[derive(Debug, Default)]
+struct FeatureIdManager {
+    ids: HashMap<String, u32>,
+}
+
+impl FeatureIdManager {
+    fn get_or_insert(&mut self, feature: &str) -> u32 {
+        if let Some(&id) = self.ids.get(feature) {
+            id
+        } else {
+            let id = self.ids.len() as u32;
+            self.ids.insert(feature.to_string(), id);
+            id
+        }
+    }
+}
+
+/// A linear discriminative classifier trained with the averaged perceptron, offered as an
+/// alternative to [`BayesianModel`] for stylometric attribution. Unlike naive Bayes, this model
+/// weighs features jointly rather than treating them as independent.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PerceptronModel {
+    /// List of authors
+    authors: Vec<String>,
+    /// Feature (token) to dense feature-vector index
+    feature_ids: HashMap<String, u32>,
+    /// Averaged weight matrix, `weights[author][feature]`
+    weights: Vec<Vec<f64>>,
+    /// Which feature families `tokenize` extracts; must match between training and classification
+    feature_config: FeatureConfig,
+    /// How word tokens are filtered before counting; must match between training and
+    /// classification, see [`TokenPipeline`]
+    pipeline: TokenPipeline,
+}
+
+pub const DEFAULT_EPOCHS: usize = 10;
+
+impl PerceptronModel {
+    /// Train the averaged perceptron using the given dataset, following the same `(author, path)`
+    /// convention as [`BayesianModel::train`]. Runs for [`DEFAULT_EPOCHS`] epochs; use
+    /// [`Self::train_with_epochs`] to control this.
+    pub fn train(dataset: Vec<(String, String)>, feature_config: FeatureConfig) -> io::Result<Self> {
+        Self::train_with_epochs(dataset, feature_config, DEFAULT_EPOCHS)
+    }
+
+    /// Train the averaged perceptron for a specific number of epochs. Each epoch, every
+    /// mis-classified document nudges the weight matrix towards the gold author and away from the
+    /// predicted one; the final weights are the running average across all epochs, which is what
+    /// stabilizes the perceptron.
+    pub fn train_with_epochs(
+        dataset: Vec<(String, String)>,
+        feature_config: FeatureConfig,
+        epochs: usize,
+    ) -> io::Result<Self> {
+        Self::train_with_config(dataset, feature_config, TokenPipeline::default(), epochs)
+    }
+
+    /// Train the averaged perceptron with full control over feature extraction, token filtering,
+    /// and epoch count. See [`FeatureConfig`] and [`TokenPipeline`].
+    pub fn train_with_config(
+        dataset: Vec<(String, String)>,
+        feature_config: FeatureConfig,
+        pipeline: TokenPipeline,
+        epochs: usize,
+    ) -> io::Result<Self> {
+        log::trace!("Find all authors.");
+        let author_dict = dataset
+            .iter()
+            .map(|(author, _)| author.clone())
+            .collect::<HashSet<_>>();
+        let authors = author_dict.into_iter().collect::<Vec<_>>();
+        let author_count = authors.len();
+        log::trace!("Contains {} authors in total.", author_count);
+
+        log::trace!("Read all documents.");
+        let texts = dataset
+            .into_iter()
+            .map(|(author, path)| {
+                log::trace!("Reading: ('{}', `{}`)", author, path);
+                let text = std::fs::read_to_string(&path)?;
+                Ok((author, text))
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let feature_config =
+            BayesianModel::learn_bpe_merges_if_configured(&texts, feature_config, &pipeline);
+
+        log::trace!("Build sparse feature vectors for each document.");
+        let mut feature_manager = FeatureIdManager::default();
+        let mut documents: Vec<(usize, Vec<(u32, f64)>)> = Vec::with_capacity(texts.len());
+        for (author, text) in &texts {
+            let gold = authors.iter().position(|name| author.eq(name)).unwrap();
+            let tokens = BayesianModel::tokenize(text, &feature_config, &pipeline);
+
+            let mut counts: HashMap<u32, f64> = HashMap::new();
+            for token in tokens {
+                let id = feature_manager.get_or_insert(&token);
+                *counts.entry(id).or_insert(0.0) += 1.0;
+            }
+            documents.push((gold, counts.into_iter().collect()));
+        }
+
+        let feature_count = feature_manager.ids.len();
+        let mut weights = vec![vec![0.0; feature_count]; author_count];
+        let mut totals = vec![vec![0.0; feature_count]; author_count];
+        // Tracks, per touched `(author, feature)` cell, the step its `totals` entry was last
+        // caught up to; see [`Self::catch_up_total`]. Sparse, unlike `weights`/`totals`, so
+        // training cost scales with how many cells actually change, not `author_count *
+        // feature_count` (which dwarfs the document count once char n-grams/BPE/word-bigrams
+        // push the vocabulary into the tens or hundreds of thousands).
+        let mut last_touched: HashMap<(usize, u32), usize> = HashMap::new();
+        let mut step: usize = 0;
+
+        log::trace!("Train for {} epochs.", epochs);
+        for epoch in 0..epochs {
+            let mut mistakes = 0;
+            for (gold, xs) in &documents {
+                step += 1;
+                let scores = Self::score(&weights, xs, author_count);
+                let predicted = Self::argmax(&scores);
+                if predicted != *gold {
+                    mistakes += 1;
+                    for &(id, value) in xs {
+                        Self::catch_up_total(&mut totals, &weights, &mut last_touched, *gold, id, step);
+                        Self::catch_up_total(
+                            &mut totals,
+                            &weights,
+                            &mut last_touched,
+                            predicted,
+                            id,
+                            step,
+                        );
+                        weights[*gold][id as usize] += value;
+                        weights[predicted][id as usize] -= value;
+                    }
+                }
+            }
+            log::trace!("Epoch {}: {} mistakes.", epoch, mistakes);
+        }
+
+        // Every touched cell is only caught up to the step it was last touched at; flush the
+        // remaining steps (up to and including the final one) through the end of training.
+        for (&(author, feature), &last_step) in &last_touched {
+            totals[author][feature as usize] +=
+                weights[author][feature as usize] * (step + 1 - last_step) as f64;
+        }
+
+        let total_steps = step as f64;
+        let weights = totals
+            .into_iter()
+            .map(|row| row.into_iter().map(|w| w / total_steps).collect())
+            .collect();
+
+        Ok(Self {
+            authors,
+            feature_ids: feature_manager.ids,
+            weights,
+            feature_config,
+            pipeline,
+        })
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), PerceptronSaveError> {
+        fsio::file::ensure_exists(&path.as_ref())?;
+        let bin_vec = persist::encode(persist::ModelKind::Perceptron, self)?;
+        std::fs::write(path, bin_vec)?;
+        Ok(())
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, PerceptronLoadError> {
+        let bin_vec = std::fs::read(path)?;
+        Ok(persist::decode(persist::ModelKind::Perceptron, &bin_vec)?)
+    }
+
+    pub fn classify_text(&self, text: &str) -> Classification {
+        let transform = |v: Vec<f32>| {
+            v.into_iter()
+                .enumerate()
+                .map(|(author, probability)| (self.authors[author].clone(), probability))
+                .collect()
+        };
+        let Predication {
+            sentences_predicate,
+            total_predicate,
+        } = self.predicate_text(text);
+        let sentences_classification = sentences_predicate
+            .into_iter()
+            .map(|(sentence_index, predication)| (sentence_index, transform(predication)))
+            .collect::<Vec<_>>();
+        let total_classification = transform(total_predicate);
+        Classification {
+            sentences_classification,
+            total_classification,
+        }
+    }
+
+    /// Beam-searches the `k` most probable full author-assignment sequences across this
+    /// document's sentences. See [`crate::model::beam_search_top_k`].
+    pub fn classify_text_top_k(&self, text: &str, k: usize) -> Vec<(Vec<String>, f64)> {
+        let predication = self.predicate_text(text);
+        crate::model::beam_search_top_k(&predication, k)
+            .into_iter()
+            .map(|(outcomes, log_prob)| {
+                (
+                    outcomes
+                        .into_iter()
+                        .map(|author| self.authors[author].clone())
+                        .collect(),
+                    log_prob,
+                )
+            })
+            .collect()
+    }
+
+    fn predicate_text(&self, text: &str) -> Predication {
+        let sentences = BayesianModel::preprocess(text, &self.feature_config, &self.pipeline);
+        let sentence_count = sentences.len();
+        let sentences_predicate = sentences
+            .into_iter()
+            .map(|(sentence_index, sentence)| (sentence_index, self.predicate(&sentence)))
+            .collect::<Vec<_>>();
+        let author_count = self.authors.len();
+        let total_predicate = sentences_predicate
+            .iter()
+            .map(|(_, sentence_probability)| sentence_probability)
+            .fold(vec![0.0; author_count], |acc, sentence_probability| {
+                acc.iter()
+                    .zip(sentence_probability.iter())
+                    .map(|(&a, &b)| a + b)
+                    .collect::<Vec<_>>()
+            });
+        let total_predicate = total_predicate
+            .into_iter()
+            .map(|probability| probability / sentence_count as f32)
+            .collect();
+        Predication {
+            sentences_predicate,
+            total_predicate,
+        }
+    }
+
+    /// Scores every author against `tokens` and softmaxes the scores into a probability
+    /// distribution. Features unseen at training time are skipped.
+    fn predicate(&self, tokens: &[String]) -> Vec<f32> {
+        let author_count = self.authors.len();
+        let mut counts: HashMap<u32, f64> = HashMap::new();
+        for token in tokens {
+            if let Some(&id) = self.feature_ids.get(token) {
+                *counts.entry(id).or_insert(0.0) += 1.0;
+            }
+        }
+        let xs = counts.into_iter().collect::<Vec<_>>();
+        let scores = Self::score(&self.weights, &xs, author_count);
+        crate::model::bayesian::softmax(&scores)
+            .into_iter()
+            .map(|p| p as f32)
+            .collect()
+    }
+
+    fn score(weights: &[Vec<f64>], xs: &[(u32, f64)], author_count: usize) -> Vec<f64> {
+        (0..author_count)
+            .map(|author| {
+                xs.iter()
+                    .map(|&(id, value)| weights[author][id as usize] * value)
+                    .sum::<f64>()
+            })
+            .collect()
+    }
+
+    /// Folds `weights[author][feature]`'s contribution into `totals[author][feature]` for every
+    /// step since it was last touched (the standard averaged-perceptron lazy-sum trick), instead
+    /// of re-summing the whole dense matrix after every document. A cell that's touched again
+    /// later, or never again, still ends up with the exact same total a dense per-step sum would
+    /// have produced — it just pays for the steps it was actually live for, in one step each time
+    /// it changes, rather than on every single document regardless of whether it changed.
+    fn catch_up_total(
+        totals: &mut [Vec<f64>],
+        weights: &[Vec<f64>],
+        last_touched: &mut HashMap<(usize, u32), usize>,
+        author: usize,
+        feature: u32,
+        step: usize,
+    ) {
+        let last_step = last_touched.insert((author, feature), step).unwrap_or(0);
+        totals[author][feature as usize] +=
+            weights[author][feature as usize] * (step - last_step) as f64;
+    }
+
+    fn argmax(scores: &[f64]) -> usize {
+        scores
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+            .map(|(index, _)| index)
+            .unwrap()
+    }
+}
+
+impl Display for PerceptronModel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Perceptron Model with {} authors and {} features.",
+            self.authors.len(),
+            self.feature_ids.len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_temp_doc(name: &str, text: &str) -> String {
+        let path =
+            std::env::temp_dir().join(format!("stal-perceptron-test-{}-{name}.txt", std::process::id()));
+        std::fs::write(&path, text).unwrap();
+        path.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn train_classify_round_trip_picks_the_closer_author() {
+        let alice = write_temp_doc(
+            "alice",
+            "the quick brown fox jumps over the lazy dog the fox runs across the yard",
+        );
+        let bob = write_temp_doc(
+            "bob",
+            "graphs have vertices and edges a directed acyclic graph has no cycles between nodes",
+        );
+        let dataset = vec![("alice".to_string(), alice.clone()), ("bob".to_string(), bob.clone())];
+
+        let model = PerceptronModel::train(dataset, FeatureConfig::default()).unwrap();
+        let classification = model.classify_text("the quick fox jumps over the lazy dog");
+        let (top_author, _) = classification
+            .total_classification
+            .iter()
+            .max_by(|a, b| a.1.partial_cmp(b.1).unwrap())
+            .unwrap();
+        assert_eq!(top_author, "alice");
+
+        std::fs::remove_file(alice).ok();
+        std::fs::remove_file(bob).ok();
+    }
+}
@@ -0,0 +1,174 @@
+//! Configurable token-filtering pipeline shared by every model kind's tokenizer, so that
+//! training-time and classification-time tokenization always line up (see
+//! [`TokenPipeline::apply`]).
+
+use std::collections::HashSet;
+
+use rust_stemmers::{Algorithm, Stemmer};
+use serde::{Deserialize, Serialize};
+
+/// Selects a language's built-in stopword list and Snowball stemming algorithm for
+/// [`TokenPipeline::for_language`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Language {
+    English,
+    SimplifiedChinese,
+}
+
+impl Language {
+    fn default_stopwords(self) -> HashSet<String> {
+        let words: &[&str] = match self {
+            Language::English => &[
+                "the", "a", "an", "and", "or", "but", "of", "to", "in", "is", "it", "that",
+                "this", "for", "on", "with", "as", "at", "by", "be", "are", "was", "were",
+            ],
+            Language::SimplifiedChinese => {
+                &["的", "了", "和", "是", "在", "我", "有", "他", "这", "也", "就", "都", "而"]
+            }
+        };
+        words.iter().map(|word| word.to_string()).collect()
+    }
+
+    fn stem_algorithm(self) -> Option<StemAlgorithm> {
+        match self {
+            Language::English => Some(StemAlgorithm::English),
+            // Snowball has no Chinese algorithm; word segmentation already does most of the work.
+            Language::SimplifiedChinese => None,
+        }
+    }
+}
+
+/// The Snowball stemming algorithms offered by `rust-stemmers`, re-declared so
+/// [`TokenPipeline`] can derive `Serialize`/`Deserialize`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StemAlgorithm {
+    Arabic,
+    Danish,
+    Dutch,
+    English,
+    Finnish,
+    French,
+    German,
+    Greek,
+    Hungarian,
+    Italian,
+    Norwegian,
+    Portuguese,
+    Romanian,
+    Russian,
+    Spanish,
+    Swedish,
+    Tamil,
+    Turkish,
+}
+
+impl From<StemAlgorithm> for Algorithm {
+    fn from(algorithm: StemAlgorithm) -> Self {
+        match algorithm {
+            StemAlgorithm::Arabic => Algorithm::Arabic,
+            StemAlgorithm::Danish => Algorithm::Danish,
+            StemAlgorithm::Dutch => Algorithm::Dutch,
+            StemAlgorithm::English => Algorithm::English,
+            StemAlgorithm::Finnish => Algorithm::Finnish,
+            StemAlgorithm::French => Algorithm::French,
+            StemAlgorithm::German => Algorithm::German,
+            StemAlgorithm::Greek => Algorithm::Greek,
+            StemAlgorithm::Hungarian => Algorithm::Hungarian,
+            StemAlgorithm::Italian => Algorithm::Italian,
+            StemAlgorithm::Norwegian => Algorithm::Norwegian,
+            StemAlgorithm::Portuguese => Algorithm::Portuguese,
+            StemAlgorithm::Romanian => Algorithm::Romanian,
+            StemAlgorithm::Russian => Algorithm::Russian,
+            StemAlgorithm::Spanish => Algorithm::Spanish,
+            StemAlgorithm::Swedish => Algorithm::Swedish,
+            StemAlgorithm::Tamil => Algorithm::Tamil,
+            StemAlgorithm::Turkish => Algorithm::Turkish,
+        }
+    }
+}
+
+/// Configures how word tokens are filtered before they're counted. Threaded through
+/// `train`/`preprocess`/`predicate` and stored inside each model so it's serialized alongside the
+/// rest of the training-time state: load-time tokenization must exactly match training-time
+/// tokenization, otherwise the token dictionary keys won't line up.
+///
+/// The default pipeline changes nothing, preserving the original raw-`charabia`-segment
+/// behavior.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TokenPipeline {
+    /// Drop tokens containing no alphanumeric character (punctuation/whitespace-only tokens).
+    pub remove_punctuation: bool,
+    /// Fold tokens to lowercase before counting.
+    pub case_fold: bool,
+    /// Tokens to drop outright after case folding, e.g. function words.
+    pub stopwords: HashSet<String>,
+    /// Snowball stemming algorithm used to collapse inflectional variants, if any.
+    pub stem_algorithm: Option<StemAlgorithm>,
+    /// The [`Language`] [`Self::for_language`] was built from, if any. Not consulted by
+    /// [`Self::apply`] (`stopwords`/`stem_algorithm` already carry its effect); kept so a saved
+    /// model records which language preset trained it, for tooling/debugging rather than
+    /// retokenization.
+    pub language: Option<Language>,
+}
+
+impl TokenPipeline {
+    /// A pipeline with sensible defaults for `language`: punctuation removal, case folding, the
+    /// language's built-in stopword list, and its Snowball stemmer (if one exists).
+    pub fn for_language(language: Language) -> Self {
+        Self {
+            remove_punctuation: true,
+            case_fold: true,
+            stopwords: language.default_stopwords(),
+            stem_algorithm: language.stem_algorithm(),
+            language: Some(language),
+        }
+    }
+
+    /// Applies the pipeline to a single word token, returning `None` if it should be dropped.
+    pub(crate) fn apply(&self, token: &str) -> Option<String> {
+        if self.remove_punctuation && !token.chars().any(char::is_alphanumeric) {
+            return None;
+        }
+
+        let token = if self.case_fold {
+            token.to_lowercase()
+        } else {
+            token.to_string()
+        };
+
+        if self.stopwords.contains(&token) {
+            return None;
+        }
+
+        let token = match self.stem_algorithm {
+            Some(algorithm) => Stemmer::create(algorithm.into()).stem(&token).into_owned(),
+            None => token,
+        };
+
+        Some(token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn for_language_records_the_language_it_was_built_from() {
+        let pipeline = TokenPipeline::for_language(Language::English);
+        assert_eq!(pipeline.language, Some(Language::English));
+    }
+
+    #[test]
+    fn for_language_drops_stopwords_and_stems() {
+        let pipeline = TokenPipeline::for_language(Language::English);
+        assert_eq!(pipeline.apply("the"), None);
+        assert_eq!(pipeline.apply("running"), Some("run".to_string()));
+    }
+
+    #[test]
+    fn default_pipeline_changes_nothing() {
+        let pipeline = TokenPipeline::default();
+        assert_eq!(pipeline.apply("The"), Some("The".to_string()));
+    }
+}
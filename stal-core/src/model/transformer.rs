@@ -0,0 +1,163 @@
+//! Optional transformer-based classification backend, selectable from the CLI the same way as
+//! [`crate::model::BayesianModel`] and [`crate::model::PerceptronModel`].
+//!
+//! Gated behind the `transformer` cargo feature so the default build stays lightweight and
+//! CPU-only: rust-bert pulls in libtorch, which is a heavy, platform-specific dependency most
+//! users of this crate don't need.
+
+use std::path::Path;
+
+use rust_bert::pipelines::sequence_classification::{
+    SequenceClassificationConfig, SequenceClassificationModel,
+};
+use rust_bert::resources::LocalResource;
+
+use crate::model::bayesian::{softmax, BayesianModel};
+use crate::model::{Classification, Predication};
+
+/// Builds a [`SequenceClassificationConfig`] that loads a fine-tuned checkpoint directory from
+/// disk, rather than one of rust-bert's bundled pretrained resources.
+pub fn transformer_config_from_checkpoint<P: AsRef<Path>>(
+    checkpoint: P,
+) -> anyhow::Result<SequenceClassificationConfig> {
+    let checkpoint = checkpoint.as_ref();
+    Ok(SequenceClassificationConfig {
+        model_resource: Box::new(LocalResource {
+            local_path: checkpoint.join("rust_model.ot"),
+        })
+        .into(),
+        config_resource: Box::new(LocalResource {
+            local_path: checkpoint.join("config.json"),
+        })
+        .into(),
+        vocab_resource: Box::new(LocalResource {
+            local_path: checkpoint.join("vocab.txt"),
+        })
+        .into(),
+        ..Default::default()
+    })
+}
+
+/// Classification backend built on a fine-tuned transformer sequence-classification checkpoint.
+///
+/// Unlike [`crate::model::BayesianModel`], fine-tuning isn't implemented here; `load` expects a
+/// checkpoint directory whose label set is `authors`, in the order the checkpoint was fine-tuned
+/// with. The `rich`/`concise` CLI formatting paths are shared with the other backends because
+/// classification still produces the same [`Classification`]/[`Predication`] shapes.
+pub struct TransformerModel {
+    authors: Vec<String>,
+    classifier: SequenceClassificationModel,
+}
+
+impl TransformerModel {
+    /// Load a fine-tuned sequence-classification checkpoint. `authors` must list the labels in
+    /// the same order the checkpoint was fine-tuned with. Sentences are split on the same
+    /// boundaries as the other backends (see [`BayesianModel::split_sentences`]) but handed to
+    /// the checkpoint's own tokenizer as raw text, since a fine-tuned transformer tokenizer
+    /// expects natural-language input, not this crate's namespaced feature tokens.
+    pub fn load(config: SequenceClassificationConfig, authors: Vec<String>) -> anyhow::Result<Self> {
+        let classifier = SequenceClassificationModel::new(config)?;
+        Ok(Self { authors, classifier })
+    }
+
+    pub fn classify_text(&self, text: &str) -> Classification {
+        let transform = |v: Vec<f32>| {
+            v.into_iter()
+                .enumerate()
+                .map(|(author, probability)| (self.authors[author].clone(), probability))
+                .collect()
+        };
+        let Predication {
+            sentences_predicate,
+            total_predicate,
+        } = self.predicate_text(text);
+        let sentences_classification = sentences_predicate
+            .into_iter()
+            .map(|(sentence_index, predication)| (sentence_index, transform(predication)))
+            .collect::<Vec<_>>();
+        let total_classification = transform(total_predicate);
+        Classification {
+            sentences_classification,
+            total_classification,
+        }
+    }
+
+    fn predicate_text(&self, text: &str) -> Predication {
+        // Unlike `BayesianModel`/`PerceptronModel`, the checkpoint's own tokenizer expects raw
+        // sentence text, not `BayesianModel::tokenize`'s namespaced feature tokens (`w:`/`b:`/
+        // `c{n}:`/`p:`) — splitting only, without tokenizing, keeps this backend from handing the
+        // classifier strings like "w:the w:quick w:brown" instead of "the quick brown".
+        let sentences = BayesianModel::split_sentences(text);
+        let sentence_count = sentences.len();
+
+        // Batch every sentence through one forward pass; rust-bert pads each batch to the
+        // longest sequence using the tokenizer's pad id internally.
+        let batch = sentences
+            .iter()
+            .map(|(_, sentence)| sentence.as_str())
+            .collect::<Vec<_>>();
+        // `predict` only surfaces the single top-scoring label, throwing away every other
+        // author's score. `predict_multilabel` runs the same forward pass but scores every label
+        // independently (sigmoid per class rather than one argmax), so we get a real score for
+        // every author instead of having to fabricate one. `threshold: 0.0` keeps all of them,
+        // since sigmoid outputs are always positive.
+        let label_sets = self
+            .classifier
+            .predict_multilabel(&batch, 0.0)
+            .expect("transformer forward pass failed");
+
+        let author_count = self.authors.len();
+        let sentences_predicate = sentences
+            .into_iter()
+            .zip(label_sets)
+            .map(|((sentence_index, _), labels)| {
+                (sentence_index, Self::labels_to_distribution(&self.authors, &labels))
+            })
+            .collect::<Vec<_>>();
+
+        let total_predicate = sentences_predicate
+            .iter()
+            .map(|(_, sentence_probability)| sentence_probability)
+            .fold(vec![0.0; author_count], |acc, sentence_probability| {
+                acc.iter()
+                    .zip(sentence_probability.iter())
+                    .map(|(&a, &b)| a + b)
+                    .collect::<Vec<_>>()
+            })
+            .into_iter()
+            .map(|probability| probability / sentence_count as f32)
+            .collect();
+
+        Predication {
+            sentences_predicate,
+            total_predicate,
+        }
+    }
+
+    /// Turns the checkpoint's real per-label scores into a calibrated per-author distribution via
+    /// a numerically safe softmax, so relative signal between non-winning authors survives
+    /// (unlike spreading `1 - top_score` evenly, which makes every losing author identical).
+    ///
+    /// Panics if `labels` names an author not in `authors`: the checkpoint's label set must match
+    /// `authors` (the order it was fine-tuned with), so this indicates a mismatched checkpoint
+    /// rather than a recoverable runtime condition.
+    fn labels_to_distribution(
+        authors: &[String],
+        labels: &[rust_bert::pipelines::sequence_classification::Label],
+    ) -> Vec<f32> {
+        let mut scores = vec![0.0f64; authors.len()];
+        for label in labels {
+            let author = authors
+                .iter()
+                .position(|name| name == &label.text)
+                .unwrap_or_else(|| {
+                    panic!(
+                        "checkpoint label `{}` is not one of this model's `authors`",
+                        label.text
+                    )
+                });
+            scores[author] = label.score;
+        }
+        softmax(&scores).into_iter().map(|p| p as f32).collect()
+    }
+}
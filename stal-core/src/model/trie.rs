@@ -0,0 +1,100 @@
+//! A character trie supporting bounded Levenshtein fuzzy lookup, used to rescue unseen tokens in
+//! [`crate::model::BayesianModel::predicate`] that are typos or spelling variants of a known
+//! token, rather than flatly discarding them.
+
+use std::collections::HashMap;
+
+#[derive(Debug)]
+struct TrieNode<V> {
+    children: HashMap<char, TrieNode<V>>,
+    value: Option<V>,
+}
+
+impl<V> Default for TrieNode<V> {
+    fn default() -> Self {
+        Self {
+            children: HashMap::new(),
+            value: None,
+        }
+    }
+}
+
+/// A trie keyed on a token's characters, storing one value `V` per complete token. Named
+/// `DynTrie` because each node's branching factor is dynamic (a `HashMap` over whatever
+/// characters were actually inserted) rather than a fixed alphabet array.
+#[derive(Debug)]
+pub(crate) struct DynTrie<V> {
+    root: TrieNode<V>,
+}
+
+impl<V> Default for DynTrie<V> {
+    fn default() -> Self {
+        Self {
+            root: TrieNode::default(),
+        }
+    }
+}
+
+impl<V> DynTrie<V> {
+    pub(crate) fn insert(&mut self, key: &str, value: V) {
+        let mut node = &mut self.root;
+        for ch in key.chars() {
+            node = node.children.entry(ch).or_default();
+        }
+        node.value = Some(value);
+    }
+
+    /// Walks the trie as a DFS, carrying the current word buffer and the previous row of the
+    /// Levenshtein edit-distance matrix, pruning any branch whose partial edit distance already
+    /// exceeds `max_distance`. Returns the closest token within the bound (by edit distance),
+    /// its value, and the distance, or `None` if nothing within `max_distance` exists.
+    pub(crate) fn fuzzy_get(&self, key: &str, max_distance: usize) -> Option<(String, &V, usize)> {
+        let key_chars = key.chars().collect::<Vec<_>>();
+        let initial_row = (0..=key_chars.len()).collect::<Vec<_>>();
+        let mut buffer = String::new();
+        let mut best: Option<(String, &V, usize)> = None;
+        self.dfs(&self.root, &key_chars, &initial_row, max_distance, &mut buffer, &mut best);
+        best
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn dfs<'a>(
+        &'a self,
+        node: &'a TrieNode<V>,
+        key_chars: &[char],
+        previous_row: &[usize],
+        max_distance: usize,
+        buffer: &mut String,
+        best: &mut Option<(String, &'a V, usize)>,
+    ) {
+        if let Some(value) = &node.value {
+            let distance = previous_row[key_chars.len()];
+            let improves = match best {
+                Some((_, _, best_distance)) => distance < *best_distance,
+                None => true,
+            };
+            if distance <= max_distance && improves {
+                *best = Some((buffer.clone(), value, distance));
+            }
+        }
+
+        for (&ch, child) in &node.children {
+            let mut row = Vec::with_capacity(previous_row.len());
+            row.push(previous_row[0] + 1);
+            for (index, &key_char) in key_chars.iter().enumerate() {
+                let substitution_cost = if key_char == ch { 0 } else { 1 };
+                row.push(
+                    (row[index] + 1)
+                        .min(previous_row[index + 1] + 1)
+                        .min(previous_row[index] + substitution_cost),
+                );
+            }
+
+            if row.iter().min().copied().unwrap_or(usize::MAX) <= max_distance {
+                buffer.push(ch);
+                self.dfs(child, key_chars, &row, max_distance, buffer, best);
+                buffer.pop();
+            }
+        }
+    }
+}